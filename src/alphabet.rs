@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::globals::{ConfigError, IteratedValue};
+
+
+const BASE64: &str    = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+const HEX: &str       = "0123456789abcdef";
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const ALNUM: &str     = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NO_DOLLAR: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_";
+
+
+/// The `Alphabet` enum lists the characters the suffixes are made of, a suffix being a number written
+/// in the base of the alphabet size, its first character as the zero digit.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+pub enum Alphabet {
+	/// `0-9a-zA-Z_$`, every character of a Solidity identifier.
+	#[default]
+	Base64,
+	/// `0-9a-f`.
+	Hex,
+	/// `a-z`.
+	Lowercase,
+	/// `0-9a-zA-Z`.
+	Alnum,
+	/// `0-9a-zA-Z_`, every character of a Vyper identifier.
+	NoDollar,
+	/// Any set of identifier characters, see `Alphabet::custom`.
+	Custom(String),
+}
+
+
+impl Alphabet {
+
+	/// The presets, in the order of the command line help.
+	pub const PRESETS: [Alphabet; 5] = [Alphabet::Base64, Alphabet::Hex, Alphabet::Lowercase, Alphabet::Alnum, Alphabet::NoDollar];
+
+
+	/// Builds a custom alphabet.
+	///
+	/// Arguments:
+	///
+	/// * `chars`: At least 2 characters, all different, each one a valid identifier character
+	/// (`0-9a-zA-Z_$`).
+	///
+	/// Returns:
+	///
+	/// The `Alphabet`, or a `ConfigError` if the characters can't make an identifier suffix.
+	pub fn custom(chars: &str) -> Result<Alphabet, ConfigError> {
+		let alphabet: Alphabet = Alphabet::Custom(chars.to_string());
+		alphabet.validate()?;
+		Ok(alphabet)
+	}
+
+
+	/// The preset with these characters, a custom alphabet otherwise.
+	pub fn from_chars(chars: &str) -> Alphabet {
+		Alphabet::PRESETS.into_iter()
+			.find(|preset| preset.chars() == chars.as_bytes())
+			.unwrap_or(Alphabet::Custom(chars.to_string()))
+	}
+
+
+	/// Checks that the alphabet has at least 2 characters, all different identifier characters.
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		let chars: &[u8] = self.chars();
+		let valid: bool  = chars.len() >= 2
+			&& chars.iter().all(|&c| c.is_ascii_alphanumeric() || c == b'_' || c == b'$')
+			&& chars.iter().enumerate().all(|(i, c)| !chars[..i].contains(c));
+		if valid {
+			Ok(())
+		} else {
+			Err(ConfigError::InvalidAlphabet(String::from_utf8_lossy(chars).into_owned()))
+		}
+	}
+
+
+	/// The characters, the digits of the suffixes from 0 to `len() - 1`.
+	pub fn chars(&self) -> &[u8] {
+		match self {
+			Alphabet::Base64    => BASE64.as_bytes(),
+			Alphabet::Hex       => HEX.as_bytes(),
+			Alphabet::Lowercase => LOWERCASE.as_bytes(),
+			Alphabet::Alnum     => ALNUM.as_bytes(),
+			Alphabet::NoDollar  => NO_DOLLAR.as_bytes(),
+			Alphabet::Custom(s) => s.as_bytes(),
+		}
+	}
+
+
+	/// The characters an identifier can start with, all but the digits.
+	pub fn leading(&self) -> Vec<u8> {
+		self.chars().iter().copied().filter(|c| !c.is_ascii_digit()).collect()
+	}
+
+
+	/// The number of characters, the base of the suffixes.
+	pub fn len(&self) -> IteratedValue {
+		self.chars().len() as IteratedValue
+	}
+
+
+	/// An alphabet is never empty once validated.
+	pub fn is_empty(&self) -> bool {
+		self.chars().is_empty()
+	}
+
+
+	/// The name of a preset, `custom` otherwise.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Alphabet::Base64    => "base64",
+			Alphabet::Hex       => "hex",
+			Alphabet::Lowercase => "lowercase",
+			Alphabet::Alnum     => "alnum",
+			Alphabet::NoDollar  => "no-dollar",
+			Alphabet::Custom(_) => "custom",
+		}
+	}
+
+
+	/// The longest suffix such that all the suffixes up to its length can be indexed by an
+	/// `IteratedValue`, e.g. 10 characters in base 64, 15 in base 16.
+	pub fn max_digits(&self) -> u32 {
+		let base: IteratedValue      = self.len().max(2);
+		let mut pass: IteratedValue  = 1;
+		let mut total: IteratedValue = 0;
+		let mut digits: u32          = 0;
+		loop {
+			pass = match pass.checked_mul(base) {
+				Some(pass) => pass,
+				None       => return digits,
+			};
+			total = match total.checked_add(pass) {
+				Some(total) => total,
+				None        => return digits,
+			};
+			digits += 1;
+		}
+	}
+
+}
+
+
+impl fmt::Display for Alphabet {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Alphabet::Custom(s) => write!(f, "{}", s),
+			preset              => write!(f, "{}", preset.name()),
+		}
+	}
+}
+
+
+/// Parses a preset name, or else the characters of a custom alphabet.
+impl FromStr for Alphabet {
+	type Err = ConfigError;
+
+	fn from_str(s: &str) -> Result<Alphabet, ConfigError> {
+		match Alphabet::PRESETS.iter().find(|preset| preset.name() == s.to_lowercase()) {
+			Some(preset) => Ok(preset.clone()),
+			None         => Alphabet::custom(s),
+		}
+	}
+}
@@ -0,0 +1,134 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::time::{Duration, Instant};
+
+use crate::globals::{ConfigError, Globals};
+use crate::keccak::Backend;
+use crate::search::{SearchReport, Searcher};
+
+
+/// The `BenchResult` struct is the measure of a search with a given backend and number of threads.
+///
+/// Properties:
+///
+/// * `backend`: The `Backend` of the selector hash.
+/// * `threads`: The number of threads.
+/// * `hashes`: The number of hashes computed.
+/// * `elapsed`: The duration of the search.
+/// * `efficiency`: The hashes per second compared to those of a single thread with the same backend,
+/// times the number of threads, 1.0 for a perfect scaling.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct BenchResult {
+	pub backend   : Backend,
+	pub threads   : usize,
+	pub hashes    : u64,
+	pub elapsed   : Duration,
+	pub efficiency: f64,
+}
+
+
+impl BenchResult {
+
+	/// The number of hashes computed per second.
+	pub fn hashes_per_sec(&self) -> f64 {
+		self.hashes as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+	}
+
+}
+
+
+/// Measures the search of `hashes` candidates of a signature, with every backend and from 1 to
+/// `max_threads` threads. No result is needed, so every run computes exactly `hashes` hashes.
+///
+/// Arguments:
+///
+/// * `signature`: The function signature of the searches.
+/// * `hashes`: The number of candidates of each search.
+/// * `backends`: The backends to measure, they must be available on this CPU.
+/// * `max_threads`: The largest number of threads to measure.
+/// * `on_result`: Called after each measure.
+///
+/// Returns:
+///
+/// The `BenchResult` of each backend and number of threads, or a `ConfigError` if the signature is invalid.
+pub fn run<F>(signature: &str, hashes: u64, backends: &[Backend], max_threads: usize, on_result: F) -> Result<Vec<BenchResult>, ConfigError>
+where
+	F: Fn(&BenchResult),
+{
+	let mut results: Vec<BenchResult> = vec![];
+
+	for &backend in backends {
+		let mut single: f64 = 0.0;
+		for threads in 1..=max_threads.max(1) {
+			let mut g: Globals = Globals::new(signature)?;
+			g.difficulty  = 3;
+			g.max_results = usize::MAX;
+			g.nn_threads  = threads;
+			g.backend     = backend;
+			g.max_hashes  = Some(hashes);
+
+			let searcher: Searcher   = Searcher::new(g)?;
+			let start: Instant       = Instant::now();
+			let report: SearchReport = searcher.run_with(|_| {});
+
+			let mut result: BenchResult = BenchResult {
+				backend,
+				threads,
+				hashes    : report.hashes,
+				elapsed   : start.elapsed(),
+				efficiency: 1.0,
+			};
+			if threads == 1 {
+				single = result.hashes_per_sec();
+			} else {
+				result.efficiency = result.hashes_per_sec() / (single * threads as f64);
+			}
+			on_result(&result);
+			results.push(result);
+		}
+	}
+
+	Ok(results)
+}
+
+
+/// The fastest measure, the best backend and number of threads for this CPU.
+pub fn best(results: &[BenchResult]) -> Option<&BenchResult> {
+	results.iter().max_by(|a, b| a.hashes_per_sec().total_cmp(&b.hashes_per_sec()))
+}
+
+
+/// The function `write_json` writes the measures to a JSON file, with what is needed to compare machines
+/// and releases.
+///
+/// Arguments:
+///
+/// * `file_name`: The path of the file to create.
+/// * `results`: The measures, see `run`.
+///
+/// Returns:
+///
+/// An `io::Result`, an error if the file can't be created or written.
+pub fn write_json(file_name: &str, results: &[BenchResult]) -> std::io::Result<()> {
+	let file: File                  = File::create(file_name)?;
+	let mut writer: BufWriter<File> = BufWriter::new(file);
+
+	writeln!(writer, "{{\"select0r_bench\":{{")?;
+	writeln!(writer, "\t\"version\":\"{}\", \"os\":\"{}\", \"arch\":\"{}\", \"cpus\":{},",
+		env!("CARGO_PKG_VERSION"), std::env::consts::OS, std::env::consts::ARCH, num_cpus::get())?;
+	writeln!(writer, "\t\"runs\":[")?;
+	for (i, res) in results.iter().enumerate() {
+		let comma: &str = if i == 0 { " " } else { "," };
+		writeln!(writer, "\t{}{{ \"backend\":\"{}\", \"lanes\":{}, \"threads\":{}, \"hashes\":{}, \"seconds\":{:.3}, \"hashes_per_sec\":{:.0}, \"efficiency\":{:.3} }}",
+			comma, res.backend.name(), res.backend.lanes(), res.threads, res.hashes, res.elapsed.as_secs_f64(), res.hashes_per_sec(), res.efficiency)?;
+	}
+	write!(writer, "\t]")?;
+	if let Some(best) = best(results) {
+		write!(writer, ",\n\t\"best\":{{ \"backend\":\"{}\", \"threads\":{}, \"hashes_per_sec\":{:.0} }}",
+			best.backend.name(), best.threads, best.hashes_per_sec())?;
+	}
+	writeln!(writer, "\n}}}}")?;
+	writer.flush()
+}
@@ -0,0 +1,263 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+use crate::globals::{Globals, IteratedValue, Placement};
+use crate::output::Output;
+use crate::selector::{count_leading_zeros, SignatureResult};
+
+
+const MAGIC: &str = "select0r-checkpoint 5";
+
+
+/// The `Checkpoint` struct is a snapshot of a running search, enough to resume it later exactly
+/// where it stopped.
+///
+/// Properties:
+///
+/// * `signature`, `difficulty`, `leading0`, `max_results`, `deterministic`, `alphabet`, `pattern`, `words`,
+/// `placement`, `separator`, `cases`, `permutation`, `type_suffix`, `output`: The settings of the search, a checkpoint
+/// can only be resumed with the same ones. The signature is kept with its placeholder and its flexible types
+/// if any, see `Globals::template`, the alphabet as its characters, the pattern as written, empty if none,
+/// and its word lists, one line by list. `cases` is the number of suffix passes in case mode, `-` if not,
+/// see `Globals::set_cases`, `permutation` the positions of the arguments that trade places, `-` if none,
+/// and `type_suffix` the number of suffix passes with flexible types, `-` without, see
+/// `Globals::set_type_suffix`. `output` is the format of the output file, written by the resumed search.
+/// * `digit`: The pass of the first suffix not searched yet, the number of characters of the suffixes.
+/// * `nn_threads`: The number of threads of the search, it may be resumed with another one.
+/// * `next`: The first index of the keyspace not given out to a thread yet, see `Globals::keyspace`.
+/// * `pending`: The ranges of indexes before `next` not searched yet, `(start, end)` with `end` excluded.
+/// * `optimal`: The best selector found so far.
+/// * `hashes`: The number of hashes computed so far.
+/// * `results`: The results collected so far, the initial signature first.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Checkpoint {
+	pub signature    : String,
+	pub difficulty   : u32,
+	pub leading0     : bool,
+	pub max_results  : usize,
+	pub deterministic: bool,
+	pub alphabet     : String,
+	pub pattern      : String,
+	pub words        : Vec<(String, Vec<String>)>,
+	pub placement    : Placement,
+	pub separator    : String,
+	pub cases        : Option<u32>,
+	pub permutation  : Option<Vec<usize>>,
+	pub type_suffix  : Option<u32>,
+	pub output       : Output,
+	pub nn_threads   : usize,
+	pub digit        : u32,
+	pub next         : IteratedValue,
+	pub pending      : Vec<(IteratedValue, IteratedValue)>,
+	pub optimal      : u32,
+	pub hashes       : u64,
+	pub results      : Vec<SignatureResult>,
+}
+
+
+impl Checkpoint {
+
+	/// Checks that the checkpoint was made by a search with the same settings as `g`, but the threads.
+	pub fn matches(&self, g: &Globals) -> bool {
+		self.signature        == g.template()
+		&& self.difficulty    == g.difficulty
+		&& self.leading0      == g.leading0
+		&& self.max_results   == g.max_results
+		&& self.deterministic == g.deterministic
+		&& self.alphabet.as_bytes() == g.alphabet.chars()
+		&& self.pattern       == g.pattern.as_ref().map_or("", |pattern| pattern.source())
+		&& self.words         == g.pattern.as_ref().map_or(&[][..], |pattern| pattern.words())
+		&& self.placement     == g.placement
+		&& self.separator     == g.separator
+		&& self.cases         == g.cases.then(|| g.digit_max - 1)
+		&& self.permutation.as_deref() == g.permutation.as_ref().map(|permutation| permutation.positions())
+		&& self.type_suffix   == g.types.as_ref().map(|types| g.digit_max / types.len() as u32 - 1)
+		&& self.output        == g.output
+		&& (1..=g.digit_max).contains(&self.digit)
+		&& self.next <= g.keyspace()
+		&& self.pending.iter().all(|&(start, end)| start < end && end <= self.next)
+		&& !self.results.is_empty()
+	}
+
+
+	/// Writes the checkpoint to a file, through a temporary file so a crash never leaves a truncated one.
+	pub fn save(&self, file_name: &str) -> io::Result<()> {
+		let tmp_name: String = format!("{}.tmp", file_name);
+		fs::write(&tmp_name, self.to_string())?;
+		fs::rename(&tmp_name, file_name)
+	}
+
+
+	/// Reads a checkpoint from a file.
+	pub fn load(file_name: &str) -> io::Result<Checkpoint> {
+		fs::read_to_string(file_name)?
+			.parse::<Checkpoint>()
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+	}
+
+}
+
+
+impl fmt::Display for Checkpoint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let pending: Vec<String> = self.pending.iter().map(|(start, end)| format!("{}-{}", start, end)).collect();
+
+		writeln!(f, "{}", MAGIC)?;
+		writeln!(f, "signature\t{}",     self.signature)?;
+		writeln!(f, "difficulty\t{}",    self.difficulty)?;
+		writeln!(f, "leading0\t{}",      self.leading0)?;
+		writeln!(f, "max_results\t{}",   self.max_results)?;
+		writeln!(f, "deterministic\t{}", self.deterministic)?;
+		writeln!(f, "alphabet\t{}",      self.alphabet)?;
+		writeln!(f, "pattern\t{}",       self.pattern)?;
+		for (name, words) in &self.words {
+			writeln!(f, "words\t{}\t{}",  name, words.join(" "))?;
+		}
+		writeln!(f, "placement\t{}",     self.placement.name())?;
+		writeln!(f, "separator\t{}",     self.separator)?;
+		match self.cases {
+			Some(passes) => writeln!(f, "cases\t{}", passes)?,
+			None         => writeln!(f, "cases\t-")?,
+		}
+		match &self.permutation {
+			Some(positions) => writeln!(f, "permutation\t{}", positions.iter().map(usize::to_string).collect::<Vec<String>>().join(" "))?,
+			None            => writeln!(f, "permutation\t-")?,
+		}
+		match self.type_suffix {
+			Some(passes) => writeln!(f, "type_suffix\t{}", passes)?,
+			None         => writeln!(f, "type_suffix\t-")?,
+		}
+		writeln!(f, "output\t{}",        format!("{:?}", self.output).to_lowercase())?;
+		writeln!(f, "nn_threads\t{}",    self.nn_threads)?;
+		writeln!(f, "digit\t{}",         self.digit)?;
+		writeln!(f, "next\t{}",          self.next)?;
+		writeln!(f, "pending\t{}",       pending.join(" "))?;
+		writeln!(f, "optimal\t{:>08x}",  self.optimal)?;
+		writeln!(f, "hashes\t{}",        self.hashes)?;
+		for res in &self.results {
+			let index: String = res.index.map_or("-".to_string(), |index| index.to_string());
+			writeln!(f, "result\t{:>08x}\t{}\t{}\t{}", res.selector, res.nbr_of_zero, index, res.signature)?;
+		}
+		Ok(())
+	}
+}
+
+
+impl FromStr for Checkpoint {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Checkpoint, String> {
+		let mut lines = s.lines();
+		if lines.next() != Some(MAGIC) {
+			return Err("not a select0r checkpoint".to_string());
+		}
+
+		fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+			value.parse::<T>().map_err(|_| format!("invalid `{}` value `{}`", key, value))
+		}
+		fn parse_hex(key: &str, value: &str) -> Result<u32, String> {
+			u32::from_str_radix(value, 16).map_err(|_| format!("invalid `{}` value `{}`", key, value))
+		}
+
+		let mut cp: Checkpoint = Checkpoint {
+			signature    : String::new(),
+			difficulty   : 0,
+			leading0     : false,
+			max_results  : 0,
+			deterministic: false,
+			alphabet     : String::new(),
+			pattern      : String::new(),
+			words        : vec![],
+			placement    : Placement::Suffix,
+			separator    : "_".to_string(),
+			cases        : None,
+			permutation  : None,
+			type_suffix  : None,
+			output       : Output::TSV,
+			nn_threads   : 0,
+			digit        : 0,
+			next         : 0,
+			pending      : vec![],
+			optimal      : u32::MAX,
+			hashes       : 0,
+			results      : vec![],
+		};
+
+		for line in lines.filter(|l| !l.is_empty()) {
+			let (key, value) = line.split_once('\t').ok_or(format!("invalid line `{}`", line))?;
+			match key {
+				"signature"     => { cp.signature     = value.to_string();},
+				"difficulty"    => { cp.difficulty    = parse(key, value)?;},
+				"leading0"      => { cp.leading0      = parse(key, value)?;},
+				"max_results"   => { cp.max_results   = parse(key, value)?;},
+				"deterministic" => { cp.deterministic = parse(key, value)?;},
+				"alphabet"      => { cp.alphabet      = value.to_string();},
+				"pattern"       => { cp.pattern       = value.to_string();},
+				"words"         => {
+					let (name, words) = value.split_once('\t').ok_or(format!("invalid `{}` value `{}`", key, value))?;
+					cp.words.push((name.to_string(), words.split_whitespace().map(str::to_string).collect()));
+				},
+				"placement"     => { cp.placement     = match value {
+										"suffix" => Placement::Suffix,
+										"prefix" => Placement::Prefix,
+										_        => return Err(format!("invalid `{}` value `{}`", key, value)),
+									};},
+				"separator"     => { cp.separator     = value.to_string();},
+				"cases"         => { cp.cases         = match value {
+					"-"    => None,
+					passes => Some(parse(key, passes)?),
+				}},
+				"permutation"   => { cp.permutation   = match value {
+					"-"       => None,
+					positions => Some(positions.split_whitespace().map(|at| parse(key, at)).collect::<Result<Vec<usize>, String>>()?),
+				}},
+				"type_suffix"   => { cp.type_suffix   = match value {
+					"-"    => None,
+					passes => Some(parse(key, passes)?),
+				}},
+				"output"        => { cp.output        = match value {
+										"tsv"  => Output::TSV,
+										"csv"  => Output::CSV,
+										"json" => Output::JSON,
+										"xml"  => Output::XML,
+										"ron"  => Output::RON,
+										_      => return Err(format!("invalid `{}` value `{}`", key, value)),
+									};},
+				"nn_threads"    => { cp.nn_threads    = parse(key, value)?;},
+				"digit"         => { cp.digit         = parse(key, value)?;},
+				"next"          => { cp.next          = parse(key, value)?;},
+				"pending"       => {
+					for range in value.split_whitespace() {
+						let (start, end) = range.split_once('-').ok_or(format!("invalid `{}` value `{}`", key, range))?;
+						cp.pending.push((parse(key, start)?, parse(key, end)?));
+					}
+				},
+				"optimal"       => { cp.optimal       = parse_hex(key, value)?;},
+				"hashes"        => { cp.hashes        = parse(key, value)?;},
+				"result"        => {
+					let mut fields = value.splitn(4, '\t');
+					let selector: u32                = parse_hex(key, fields.next().unwrap_or(""))?;
+					let nbr_of_zero: u32             = parse(key, fields.next().unwrap_or(""))?;
+					let index: Option<IteratedValue> = match fields.next().unwrap_or("") {
+						"-"   => None,
+						index => Some(parse(key, index)?),
+					};
+					let signature: &str              = fields.next().ok_or(format!("invalid `{}` value `{}`", key, value))?;
+					cp.results.push(SignatureResult {
+						signature   : signature.to_string(),
+						selector,
+						leading_zero: count_leading_zeros(selector),
+						nbr_of_zero,
+						index,
+					});
+				},
+				_               => return Err(format!("unknown key `{}`", key)),
+			}
+		}
+
+		Ok(cp)
+	}
+}
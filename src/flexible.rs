@@ -0,0 +1,184 @@
+use crate::globals::ConfigError;
+use crate::permutation::split_arguments;
+
+
+/// The most combinations of flexible types in a signature.
+pub const MAX_COMBINATIONS: usize = 1024;
+
+
+/// The `FlexibleTypes` struct holds the arguments of a signature whose types may be replaced by others,
+/// given as sets of alternatives, e.g. `(uint{256|128|96},address)` or `({uint256|bytes32})`.
+///
+/// The combinations follow the order of the alternatives, the first set the most significant, so the
+/// first combination, the first alternative of each set, is the initial one.
+///
+/// Properties:
+///
+/// * `source`: The arguments as written, with their sets.
+/// * `combinations`: The arguments of each combination, parenthesis included.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct FlexibleTypes {
+	source      : String,
+	combinations: Vec<String>,
+}
+
+
+impl FlexibleTypes {
+
+	/// Parses the arguments of a signature with sets of types.
+	///
+	/// Arguments:
+	///
+	/// * `args`: The arguments part of the signature, parenthesis included, with at least one `{a|b}` set
+	/// of different alternatives, e.g. `(uint{256|128},address)`.
+	///
+	/// Returns:
+	///
+	/// A `FlexibleTypes`, or a `ConfigError` if a set is empty or not closed, an alternative is empty, repeated
+	/// or has characters not valid in a type, or if there are more than `MAX_COMBINATIONS` combinations.
+	pub fn new(args: &str) -> Result<FlexibleTypes, ConfigError> {
+		let invalid = || ConfigError::InvalidTypes(args.to_string());
+		let valid   = |c: char| c.is_ascii_alphanumeric() || "(),[]".contains(c);
+
+		// Fixed text and sets in turn, the fixed text of a set is empty
+		let mut combinations: Vec<String> = vec![String::new()];
+		let mut rest: &str                = args;
+		let mut sets: usize               = 0;
+		while let Some(open) = rest.find('{') {
+			let close: usize             = rest[open..].find('}').ok_or_else(invalid)? + open;
+			let alternatives: Vec<&str> = rest[open + 1..close].split('|').collect();
+			let fixed: &str              = &rest[..open];
+			if !fixed.chars().all(valid) || alternatives.iter().any(|alt| alt.is_empty() || !alt.chars().all(valid))
+				|| alternatives.iter().enumerate().any(|(i, alt)| alternatives[..i].contains(alt))
+				|| combinations.len() * alternatives.len() > MAX_COMBINATIONS {
+				return Err(invalid());
+			}
+			combinations = combinations.iter()
+				.flat_map(|head| alternatives.iter().map(move |alt| format!("{}{}{}", head, fixed, alt)))
+				.collect();
+			rest  = &rest[close + 1..];
+			sets += 1;
+		}
+		if sets == 0 || !rest.chars().all(valid) {
+			return Err(invalid());
+		}
+		for combination in &mut combinations {
+			combination.push_str(rest);
+		}
+
+		Ok(FlexibleTypes {
+			source: args.to_string(),
+			combinations,
+		})
+	}
+
+
+	/// The arguments as written, with their sets.
+	pub fn source(&self) -> &str {
+		&self.source
+	}
+
+
+	/// The number of combinations of the types.
+	pub fn len(&self) -> usize {
+		self.combinations.len()
+	}
+
+
+	/// Always `false`, there is at least the initial combination.
+	pub fn is_empty(&self) -> bool {
+		false
+	}
+
+
+	/// The arguments of a combination, parenthesis included.
+	pub fn combination(&self, i: usize) -> &str {
+		&self.combinations[i]
+	}
+
+
+	/// The arguments of the initial combination, the first alternative of each set.
+	pub fn initial(&self) -> &str {
+		&self.combinations[0]
+	}
+
+
+	/// Describes how the ABI encoding of some arguments differs from the initial ones, one note by replaced
+	/// argument, e.g. `#1 uint96: same size, reverts out of range`.
+	///
+	/// Arguments:
+	///
+	/// * `args`: The arguments of a combination, parenthesis included.
+	///
+	/// Returns:
+	///
+	/// The notes separated by `; `, `unchanged` if every type is the initial one.
+	pub fn encoding(&self, args: &str) -> String {
+		let notes: Vec<String> = split_arguments(self.initial()).into_iter()
+			.zip(split_arguments(args))
+			.enumerate()
+			.filter(|(_, (from, to))| from != to)
+			.map(|(i, (from, to))| format!("#{} {}: {}", i + 1, to, implication(from, to)))
+			.collect();
+		if notes.is_empty() {
+			return "unchanged".to_string();
+		}
+		notes.join("; ")
+	}
+
+}
+
+
+/// The size of a type in the head of the encoding, `None` if it is dynamic.
+fn static_size(ty: &str) -> Option<usize> {
+	if let Some(inner) = ty.strip_suffix(']') {
+		// `T[]` is dynamic, `T[k]` as dynamic as `T`
+		let (element, len) = inner.rsplit_once('[')?;
+		let len: usize     = len.parse().ok()?;
+		return static_size(element).map(|size| size * len);
+	}
+	if ty.starts_with('(') {
+		return split_arguments(ty).into_iter().map(static_size).sum();
+	}
+	match ty {
+		"bytes" | "string" => None,
+		_                  => Some(32),
+	}
+}
+
+
+/// The number of bits of the values of an elementary type, and whether they are signed.
+fn value_bits(ty: &str) -> Option<(u32, bool)> {
+	let bits = |digits: &str, default: u32| if digits.is_empty() { Some(default) } else { digits.parse::<u32>().ok() };
+	match ty {
+		"address" => Some((160, false)),
+		"bool"    => Some((1, false)),
+		_ if ty.starts_with("uint")  => bits(&ty[4..], 256).map(|n| (n, false)),
+		_ if ty.starts_with("int")   => bits(&ty[3..], 256).map(|n| (n, true)),
+		_ if ty.starts_with("bytes") => ty[5..].parse::<u32>().ok().map(|n| (8 * n, false)),
+		_ => None,
+	}
+}
+
+
+/// How replacing the type of an argument changes its encoding.
+fn implication(from: &str, to: &str) -> String {
+	match (static_size(from), static_size(to)) {
+		(Some(a), Some(b)) if a != b => format!("{} bytes in place instead of {}", b, a),
+		(Some(_), Some(_))           => {
+			let left = |ty: &str| ty.starts_with("bytes");
+			match (value_bits(from), value_bits(to)) {
+				_ if left(from) != left(to) => format!("same size, {}-aligned instead of {}-aligned",
+					if left(to) { "left" } else { "right" }, if left(from) { "left" } else { "right" }),
+				(Some((a, signed_a)), Some((b, signed_b))) if b < a || signed_a != signed_b => "same size, reverts out of range".to_string(),
+				(Some(_), Some(_))          => "same size, same encoding".to_string(),
+				_                           => "same size".to_string(),
+			}
+		},
+		(Some(_), None)              => "dynamic, an offset in place and the data at the end".to_string(),
+		(None, Some(b))              => format!("static, {} bytes in place", b),
+		(None, None)                 => "dynamic, the data at the end".to_string(),
+	}
+}
@@ -0,0 +1,579 @@
+use std::fmt;
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+
+use crate::alphabet::Alphabet;
+use crate::flexible::FlexibleTypes;
+use crate::keccak::Backend;
+use crate::output::Output;
+use crate::permutation::{split_arguments, Permutation};
+use crate::pattern::{Class, Pattern};
+
+
+pub type  IteratedValue           = u64;
+pub type  AtomicIteratedValue     = AtomicU64;
+
+
+/// The `ConfigError` enum lists the reasons why a `Globals` configuration can be rejected.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum ConfigError {
+	/// The signature has no parenthesis, or no function name before it.
+	InvalidSignature(String),
+	/// The minimal number of zero bytes is out of the [1..3] domain.
+	InvalidDifficulty(u32),
+	/// At least one result is needed.
+	InvalidMaxResults(usize),
+	/// At least one thread is needed.
+	InvalidThreads(usize),
+	/// The leading zeros stop condition is out of the [1..4] domain.
+	InvalidMinLeadingZero(u32),
+	/// The checkpoint was made by a search with other settings.
+	CheckpointMismatch,
+	/// The CPU doesn't support the hash backend.
+	UnavailableBackend(Backend),
+	/// The suffix characters are less than 2, repeated, or not valid in an identifier.
+	InvalidAlphabet(String),
+	/// The number of passes is 0, or its keyspace doesn't fit in an `IteratedValue`.
+	InvalidDigitMax(u32),
+	/// The separator has characters not valid in an identifier.
+	InvalidSeparator(String),
+	/// The generated characters start the name, but the alphabet, or some pass of the pattern, can only start
+	/// them with a digit, and an identifier can't start with one.
+	NoLeadingChar(String),
+	/// The pattern can't be parsed, uses an unknown word list, or its keyspace is empty or too large.
+	InvalidPattern(String),
+	/// A word list is empty, or a word has characters not valid in an identifier.
+	InvalidWord(String),
+	/// Less than 2 arguments trade places, too many, or not those of the signature.
+	InvalidPermutation(String),
+	/// The sets of flexible types can't be parsed, have too many combinations, or are combined with the
+	/// case or permutation modes.
+	InvalidTypes(String),
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ConfigError::InvalidSignature(s)      => write!(f, "invalid Solidity signature `{}`", s),
+			ConfigError::InvalidDifficulty(d)     => write!(f, "invalid number of zeros {} (expected 1..3)", d),
+			ConfigError::InvalidMaxResults(r)     => write!(f, "invalid max results {} (expected at least 1)", r),
+			ConfigError::InvalidThreads(t)        => write!(f, "invalid number of threads {} (expected at least 1)", t),
+			ConfigError::InvalidMinLeadingZero(k) => write!(f, "invalid number of leading zeros {} (expected 1..4)", k),
+			ConfigError::CheckpointMismatch       => write!(f, "checkpoint made with other settings"),
+			ConfigError::UnavailableBackend(b)    => write!(f, "{} backend not supported by this CPU", b.name()),
+			ConfigError::InvalidAlphabet(a)       => write!(f, "invalid alphabet `{}` (expected at least 2 different characters of 0-9a-zA-Z_$)", a),
+			ConfigError::InvalidDigitMax(d)       => write!(f, "invalid number of passes {} (expected 1..the longest suffix of the alphabet)", d),
+			ConfigError::InvalidSeparator(s)      => write!(f, "invalid separator `{}` (expected characters of 0-9a-zA-Z_$)", s),
+			ConfigError::NoLeadingChar(a)         => write!(f, "generated characters of `{}` starting with a digit only, an identifier can't start with one", a),
+			ConfigError::InvalidPattern(p)        => write!(f, "invalid pattern `{}` (expected characters and [...] classes of 0-9a-zA-Z_$, {{list}} words, maybe repeated {{n}} or {{n,m}})", p),
+			ConfigError::InvalidWord(w)           => write!(f, "invalid word `{}` (expected characters of 0-9a-zA-Z_$)", w),
+			ConfigError::InvalidPermutation(a)    => write!(f, "invalid argument permutation of `{}` (expected 2..20 different positions of its arguments)", a),
+			ConfigError::InvalidTypes(t)          => write!(f, "invalid flexible types `{}` (expected {{a|b}} sets of different types, without case or permutation mode)", t),
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
+
+
+/// The `Placement` enum tells where the generated characters go in the function name.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum Placement {
+	/// After the name and the separator, e.g. `deposit_x7`.
+	Suffix,
+	/// Before the separator and the name, e.g. `x7_deposit`. The first character is never a digit.
+	Prefix,
+}
+
+
+impl Placement {
+
+	/// The name of the placement, as written in a checkpoint.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Placement::Suffix => "suffix",
+			Placement::Prefix => "prefix",
+		}
+	}
+
+}
+
+
+/// The `Globals` struct holds the settings of a search.
+///
+/// Properties:
+///
+/// * `signature`: The initial function signature, e.g. `deposit(uint256)`, without placeholder.
+/// * `part_name`: The function name part of the signature, before the parenthesis.
+/// * `placeholder`: Where the generated characters go in `part_name`, given by a `{}` in the signature,
+/// e.g. 8 for `transfer{}From(address,address,uint256)`. Takes over `placement` and `separator`.
+/// * `part_args`: The arguments part of the signature, parenthesis included, the initial combination of
+/// the flexible types if any.
+/// * `types`: The sets of types some arguments may take instead, given by `{a|b}` in the signature, e.g.
+/// `deposit(uint{256|128|96},address)`, see `set_type_suffix`.
+/// * `difficulty`: The minimal number of zero bytes a selector must have to be kept.
+/// * `nn_threads`: The number of threads used for the search.
+/// * `backend`: The implementation of the selector hash, the fastest one of the CPU by default.
+/// * `alphabet`: The characters of the suffixes, see `set_alphabet`.
+/// * `pattern`: The characters of each position of the suffixes, instead of the alphabet, see `set_pattern`.
+/// * `placement`: Where the generated characters go, after or before the function name.
+/// * `separator`: Between the function name and the generated characters, `_` by default, maybe empty.
+/// * `cases`: The case variants of the letters of the function name are searched too, see `set_cases`.
+/// * `permutation`: The orders of the arguments are searched instead of generated characters, see
+/// `set_permutation`.
+/// * `digit_max`: The number of passes, one pass by number of characters in the suffix, up to the
+/// longest suffix whose values all fit in an `IteratedValue` (10 characters in base 64), or one pass by
+/// expansion of the pattern. In case mode, the first pass has no generated characters. A single pass of
+/// the orders of the arguments in permutation mode. With flexible types, one pass by combination of the
+/// types and pass of the suffixes, see `generated_pass`. Set again from every generator by each setter.
+/// * `leading0`: Search for leading zeros in priority.
+/// * `max_results`: The number of results needed, the initial signature included.
+/// * `deterministic`: The results are the first ones in the keyspace order, pass then value, whatever the
+/// number of threads and their scheduling.
+/// * `output`: The file format output.
+/// * `checkpoint_every`: How often a running search reports a `Checkpoint`, never if `None`.
+/// * `progress_every`: How often a running search reports its `Progress`, never if `None`.
+/// * `deadline`: Stop the search after this duration.
+/// * `max_hashes`: Stop the search after this number of hashes, counted from the resumed checkpoint if any.
+/// * `min_leading_zero`: Stop the search at the first result with at least this number of leading zeros.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Globals {
+	pub signature       : String,
+	pub part_name       : String,
+	pub placeholder     : Option<usize>,
+	pub part_args       : String,
+	pub types           : Option<FlexibleTypes>,
+	pub difficulty      : u32,
+	pub nn_threads      : usize,
+	pub backend         : Backend,
+	pub alphabet        : Alphabet,
+	pub pattern         : Option<Pattern>,
+	pub placement       : Placement,
+	pub separator       : String,
+	pub cases           : bool,
+	pub permutation     : Option<Permutation>,
+	pub digit_max       : u32,
+	pub leading0        : bool,
+	pub max_results     : usize,
+	pub deterministic   : bool,
+	pub output          : Output,
+	pub checkpoint_every: Option<Duration>,
+	pub progress_every  : Option<Duration>,
+	pub deadline        : Option<Duration>,
+	pub max_hashes      : Option<u64>,
+	pub min_leading_zero: Option<u32>,
+	cases_suffix        : u32,
+	types_suffix        : u32,
+}
+
+
+impl Globals {
+
+	/// Builds a `Globals` for the given signature, with the same defaults as the command line.
+	///
+	/// Arguments:
+	///
+	/// * `signature`: A valid Solidity signature, no spaces, no parameter names. The function name may have
+	/// a `{}` placeholder, where the generated characters go, and the arguments `{a|b}` sets of types.
+	///
+	/// Returns:
+	///
+	/// A `Globals` struct, or a `ConfigError` if the signature can't be split into a name and arguments.
+	pub fn new(signature: &str) -> Result<Globals, ConfigError> {
+		let placeholder: Option<usize> = signature.find("{}");
+		let plain: String              = signature.replacen("{}", "", 1);
+		let parenthesis: usize         = match plain.find('(') {
+			Some(p) if p > 0 && placeholder.is_none_or(|at| at <= p) && !plain[..p].contains(['{', '}']) => p,
+			_ => return Err(ConfigError::InvalidSignature(signature.to_string())),
+		};
+		let part_n: &str                 = &plain[..parenthesis];
+		let types: Option<FlexibleTypes> = match &plain[parenthesis..] {
+			args if args.contains(['{', '}']) => Some(FlexibleTypes::new(args)?),
+			_                                 => None,
+		};
+		let part_a: &str                 = types.as_ref().map_or(&plain[parenthesis..], |types| types.initial());
+
+		Ok(Globals {
+			signature       : format!("{}{}", part_n, part_a),
+			part_name       : part_n.to_owned(),
+			placeholder,
+			part_args       : part_a.to_owned(),
+			difficulty      : 2,
+			nn_threads      : 2,
+			backend         : Backend::detect(),
+			alphabet        : Alphabet::Base64,
+			pattern         : None,
+			placement       : Placement::Suffix,
+			separator       : "_".to_string(),
+			cases           : false,
+			permutation     : None,
+			digit_max       : types.as_ref().map_or(Alphabet::Base64.max_digits(), |types| types.len() as u32),
+			types,
+			leading0        : false,
+			max_results     : 4,
+			deterministic   : false,
+			output          : Output::TSV,
+			checkpoint_every: None,
+			progress_every  : None,
+			deadline        : None,
+			max_hashes      : None,
+			min_leading_zero: None,
+			cases_suffix    : 0,
+			types_suffix    : 0,
+		})
+	}
+
+
+	/// Replaces the characters of the suffixes, with as many passes as its keyspace allows.
+	pub fn set_alphabet(&mut self, alphabet: Alphabet) {
+		self.alphabet = alphabet;
+		self.update_digit_max();
+	}
+
+
+	/// Replaces the alphabet by a pattern, with one pass by expansion of its repetitions.
+	pub fn set_pattern(&mut self, pattern: Pattern) {
+		self.pattern = Some(pattern);
+		self.update_digit_max();
+	}
+
+
+	/// Searches the case variants of the function name, e.g. `dePosit` or `Deposit`, first alone, then with
+	/// up to `suffix_passes` passes of generated characters, at most the passes of the alphabet or the
+	/// pattern.
+	pub fn set_cases(&mut self, suffix_passes: u32) {
+		self.cases        = true;
+		self.cases_suffix = suffix_passes;
+		self.update_digit_max();
+	}
+
+
+	/// Searches the orders of the arguments of the signature instead, e.g. `f(bool,address)` for
+	/// `f(address,bool)`, the function name untouched. The generated characters are then ignored.
+	pub fn set_permutation(&mut self, permutation: Permutation) {
+		self.permutation = Some(permutation);
+		self.update_digit_max();
+	}
+
+
+	/// With flexible types, searches every combination of the types alone, then with up to `suffix_passes`
+	/// passes of generated characters, at most the passes of the alphabet or the pattern. Only the types by
+	/// default, nothing to do without flexible types.
+	pub fn set_type_suffix(&mut self, suffix_passes: u32) {
+		self.types_suffix = suffix_passes;
+		self.update_digit_max();
+	}
+
+
+	/// Sets `digit_max` from every generator, whatever the order of the setters.
+	fn update_digit_max(&mut self) {
+		let passes: u32 = self.suffix_passes();
+		self.digit_max  = match &self.types {
+			_ if self.permutation.is_some() => 1,
+			_ if self.cases                 => self.cases_suffix.min(passes) + 1,
+			Some(types)                     => (self.types_suffix.min(passes) + 1) * types.len() as u32,
+			None                            => passes,
+		};
+	}
+
+
+	/// The passes of generated characters of the pattern, or else of the alphabet.
+	fn suffix_passes(&self) -> u32 {
+		self.pattern.as_ref().map_or(self.alphabet.max_digits(), Pattern::passes)
+	}
+
+
+	/// The signature as given to `new`, with its placeholder and its sets of types if any.
+	pub fn template(&self) -> String {
+		let args: &str = self.types.as_ref().map_or(&self.part_args, |types| types.source());
+		match self.placeholder {
+			Some(at) => format!("{}{{}}{}{}", &self.part_name[..at], &self.part_name[at..], args),
+			None     => format!("{}{}", self.part_name, args),
+		}
+	}
+
+
+	/// The pass of the alphabet or of the pattern giving the generated characters of a pass, 0 if none.
+	///
+	/// In case mode, the first pass is the function name alone. With flexible types, the passes of the
+	/// suffixes follow each other, each one with every combination of the types in turn, the first ones
+	/// without suffix.
+	pub fn generated_pass(&self, digit: u32) -> u32 {
+		match &self.types {
+			_ if self.permutation.is_some() => 0,
+			_ if self.cases                 => digit - 1,
+			Some(types)                     => (digit - 1) / types.len() as u32,
+			None                            => digit,
+		}
+	}
+
+
+	/// The arguments of the candidates of a pass, parenthesis included, a combination of the flexible types.
+	pub fn arguments(&self, digit: u32) -> &str {
+		match &self.types {
+			Some(types) => types.combination((digit - 1) as usize % types.len()),
+			None        => &self.part_args,
+		}
+	}
+
+
+	/// The fixed part of the candidates of a pass before the generated characters, none in case mode, the
+	/// name and the opening parenthesis in permutation mode, the whole name if nothing is generated.
+	pub fn head(&self, digit: u32) -> String {
+		if self.permutation.is_some() {
+			return format!("{}(", self.part_name);
+		}
+		if self.cases {
+			return String::new();
+		}
+		if self.generated_pass(digit) == 0 {
+			return self.part_name.clone();
+		}
+		match (self.placeholder, self.placement) {
+			(Some(at), _)             => self.part_name[..at].to_string(),
+			(None, Placement::Suffix) => format!("{}{}", self.part_name, self.separator),
+			(None, Placement::Prefix) => String::new(),
+		}
+	}
+
+
+	/// The fixed part of the candidates of a pass after the generated characters, the arguments included,
+	/// only them in case mode or if nothing is generated, only the closing parenthesis in permutation mode.
+	pub fn tail(&self, digit: u32) -> String {
+		if self.permutation.is_some() {
+			return ")".to_string();
+		}
+		let args: &str = self.arguments(digit);
+		if self.cases || self.generated_pass(digit) == 0 {
+			return args.to_string();
+		}
+		match (self.placeholder, self.placement) {
+			(Some(at), _)             => format!("{}{}", &self.part_name[at..], args),
+			(None, Placement::Suffix) => args.to_string(),
+			(None, Placement::Prefix) => format!("{}{}{}", self.separator, self.part_name, args),
+		}
+	}
+
+
+	/// The generated characters start the function name, so the first one can't be a digit.
+	pub fn starts_name(&self) -> bool {
+		match self.placeholder {
+			Some(at) => at == 0,
+			None     => self.placement == Placement::Prefix,
+		}
+	}
+
+
+	/// The tokens allowed at each position of the generated part of a pass, `digit` characters of the
+	/// alphabet, or the classes of the pass of the pattern.
+	///
+	/// In case mode, the whole function name is generated : each of its letters in both cases, the
+	/// original one first, with the generated characters of the pass before where they go. None in
+	/// permutation mode, see `Permutation::write`.
+	pub fn classes(&self, digit: u32) -> Vec<Class> {
+		if self.permutation.is_some() {
+			return vec![];
+		}
+		let mut classes: Vec<Class> = if self.cases {
+			self.case_classes(digit)
+		} else {
+			self.generated(self.generated_pass(digit))
+		};
+		if let Some(first) = classes.first_mut().filter(|_| self.cases || self.starts_name()) {
+			first.retain_leading();
+		}
+		classes
+	}
+
+
+	/// The tokens of a pass of the alphabet or of the pattern, none for the pass 0.
+	fn generated(&self, pass: u32) -> Vec<Class> {
+		match &self.pattern {
+			_ if pass == 0 => vec![],
+			Some(pattern)  => pattern.classes(pass).to_vec(),
+			None           => vec![Class::chars(self.alphabet.chars()); pass as usize],
+		}
+	}
+
+
+	/// The tokens of a pass in case mode, the function name alone in the first one.
+	fn case_classes(&self, digit: u32) -> Vec<Class> {
+		let name: Vec<Class> = self.part_name.bytes()
+			.map(|c| if c.is_ascii_alphabetic() { Class::chars(&[c, c ^ 0x20]) } else { Class::chars(&[c]) })
+			.collect();
+		if digit == 1 {
+			return name;
+		}
+
+		let generated: Vec<Class> = self.generated(self.generated_pass(digit));
+		let separator: Vec<Class> = self.separator.bytes().map(|c| Class::chars(&[c])).collect();
+		match (self.placeholder, self.placement) {
+			(Some(at), _)             => [&name[..at], &generated, &name[at..]].concat(),
+			(None, Placement::Suffix) => [name, separator, generated].concat(),
+			(None, Placement::Prefix) => [generated, separator, name].concat(),
+		}
+	}
+
+
+	/// The number of generated characters of a pass.
+	pub fn suffix_len(&self, digit: u32) -> usize {
+		if let Some(permutation) = &self.permutation {
+			return permutation.width();
+		}
+		if self.cases {
+			return self.classes(digit).iter().map(Class::width).sum();
+		}
+		let pass: u32 = self.generated_pass(digit);
+		match &self.pattern {
+			_ if pass == 0 => 0,
+			Some(pattern)  => pattern.classes(pass).iter().map(Class::width).sum(),
+			None           => pass as usize,
+		}
+	}
+
+
+	/// The number of suffixes of a pass, the product of the sizes of its classes.
+	pub fn pass_len(&self, digit: u32) -> IteratedValue {
+		if let Some(permutation) = &self.permutation {
+			return permutation.len();
+		}
+		if self.cases {
+			return self.classes(digit).iter().map(|class| class.len() as IteratedValue).product();
+		}
+		let pass: u32 = self.generated_pass(digit);
+		match &self.pattern {
+			_ if pass == 0 => 1,
+			Some(pattern)  => {
+				let classes: &[Class]      = pattern.classes(pass);
+				let leading: IteratedValue = if self.starts_name() { classes[0].leading_len() } else { classes[0].len() } as IteratedValue;
+				leading * classes[1..].iter().map(|class| class.len() as IteratedValue).product::<IteratedValue>()
+			},
+			None           => {
+				let leading: IteratedValue = if self.starts_name() { self.alphabet.leading().len() as IteratedValue } else { self.alphabet.len() };
+				leading * self.alphabet.len().pow(pass - 1)
+			},
+		}
+	}
+
+
+	/// The index of the first suffix of a pass in the keyspace, where the passes follow each other.
+	pub fn pass_start(&self, digit: u32) -> IteratedValue {
+		(1..digit).map(|d| self.pass_len(d)).sum()
+	}
+
+
+	/// The number of suffixes of all the passes.
+	pub fn keyspace(&self) -> IteratedValue {
+		self.pass_start(self.digit_max + 1)
+	}
+
+
+	/// Finds the suffix at an index of the keyspace.
+	///
+	/// Arguments:
+	///
+	/// * `index`: An index of the keyspace, the last pass if past its end.
+	///
+	/// Returns:
+	///
+	/// The pass of the suffix, and its value in the pass.
+	pub fn locate(&self, index: IteratedValue) -> (u32, IteratedValue) {
+		let mut value: IteratedValue = index;
+		for digit in 1..self.digit_max {
+			if value < self.pass_len(digit) {
+				return (digit, value);
+			}
+			value -= self.pass_len(digit);
+		}
+		(self.digit_max, value)
+	}
+
+
+	/// Checks that every setting is within its domain.
+	///
+	/// Returns:
+	///
+	/// `Ok(())` if the configuration can be used by a `Searcher`, the first `ConfigError` found otherwise.
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.part_name.is_empty() || !self.part_args.starts_with('(') || !self.part_args.ends_with(')') {
+			return Err(ConfigError::InvalidSignature(self.signature.clone()));
+		}
+		if !(1..=3).contains(&self.difficulty) {
+			return Err(ConfigError::InvalidDifficulty(self.difficulty));
+		}
+		if self.max_results < 1 {
+			return Err(ConfigError::InvalidMaxResults(self.max_results));
+		}
+		if self.nn_threads < 1 {
+			return Err(ConfigError::InvalidThreads(self.nn_threads));
+		}
+		if let Some(k) = self.min_leading_zero.filter(|k| !(1..=4).contains(k)) {
+			return Err(ConfigError::InvalidMinLeadingZero(k));
+		}
+		if !self.backend.is_available() {
+			return Err(ConfigError::UnavailableBackend(self.backend));
+		}
+		if self.placeholder.is_some_and(|at| at > self.part_name.len() || !self.part_name.is_char_boundary(at)) {
+			return Err(ConfigError::InvalidSignature(self.signature.clone()));
+		}
+		if !self.starts_name() && self.part_name.starts_with(|c: char| c.is_ascii_digit()) {
+			return Err(ConfigError::InvalidSignature(self.signature.clone()));
+		}
+		if !self.separator.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'$') {
+			return Err(ConfigError::InvalidSeparator(self.separator.clone()));
+		}
+		if let Some(types) = self.types.as_ref().filter(|_| self.cases || self.permutation.is_some()) {
+			return Err(ConfigError::InvalidTypes(types.source().to_string()));
+		}
+		if let Some(permutation) = &self.permutation {
+			if permutation.arguments().iter().map(String::as_str).ne(split_arguments(&self.part_args)) {
+				return Err(ConfigError::InvalidPermutation(self.part_args.clone()));
+			}
+			if self.digit_max != 1 {
+				return Err(ConfigError::InvalidDigitMax(self.digit_max));
+			}
+			return Ok(());
+		}
+		let passes: u32 = match &self.pattern {
+			Some(pattern) => {
+				if self.starts_name() && (1..=pattern.passes()).any(|digit| pattern.classes(digit)[0].leading_len() == 0) {
+					return Err(ConfigError::NoLeadingChar(pattern.to_string()));
+				}
+				pattern.passes()
+			},
+			None          => {
+				self.alphabet.validate()?;
+				if self.starts_name() && self.alphabet.leading().is_empty() {
+					return Err(ConfigError::NoLeadingChar(self.alphabet.to_string()));
+				}
+				self.alphabet.max_digits()
+			},
+		};
+
+		// The variants of the name, or the combinations of the types, multiply the keyspace of each pass
+		let fits = || (1..=self.digit_max).try_fold(0 as IteratedValue, |total, digit| {
+			self.classes(digit).iter()
+				.try_fold(1 as IteratedValue, |len, class| len.checked_mul(class.len() as IteratedValue))
+				.and_then(|len| total.checked_add(len))
+		}).is_some();
+		let valid: bool = match &self.types {
+			_ if self.cases => (1..=passes + 1).contains(&self.digit_max) && fits(),
+			Some(types)     => {
+				let combinations: u32 = types.len() as u32;
+				self.digit_max.is_multiple_of(combinations) && (1..=(passes + 1) * combinations).contains(&self.digit_max) && fits()
+			},
+			None            => (1..=passes).contains(&self.digit_max),
+		};
+		if !valid {
+			return Err(ConfigError::InvalidDigitMax(self.digit_max));
+		}
+		Ok(())
+	}
+
+}
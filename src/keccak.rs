@@ -0,0 +1,558 @@
+//! Keccak-256, reduced to what a selector needs : the first 4 bytes of the hash.
+//!
+//! Almost every signature fits in a single 136 bytes block, so the padding is done in place in that
+//! block, one keccak-f[1600] permutation is run and only the first lane of the state is read back.
+
+use std::fmt;
+
+
+/// Size of a block, in bytes (1088 bits of rate for Keccak-256).
+pub const RATE: usize = 136;
+
+/// Size of a block, in lanes of 64 bits.
+pub const RATE_LANES: usize = RATE / 8;
+
+pub(crate) const RC: [u64; 24] = [
+	0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+	0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+	0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+	0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+	0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+	0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// Rotation offsets and lane positions of the rho and pi steps, walking the lanes from `a[1]`
+pub(crate) const RHO: [u32; 24] = [
+	 1,  3,  6, 10, 15, 21, 28, 36, 45, 55,  2, 14,
+	27, 41, 56,  8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+pub(crate) const PI: [usize; 24] = [
+	10,  7, 11, 17, 18,  3,  5, 16,  8, 21, 24,  4,
+	15, 23, 19, 13, 12,  2, 20, 14, 22,  9,  6,  1,
+];
+
+
+/// The operations of the permutation on the same lane of `N` independent states : a `u64` for a
+/// single state, an array of `u64` the compiler may vectorise, or a SIMD register.
+pub(crate) trait Lanes<const N: usize>: Copy {
+	fn splat(lane: u64) -> Self;
+	fn load(lanes: [u64; N]) -> Self;
+	fn store(self) -> [u64; N];
+	fn xor(self, other: Self) -> Self;
+	fn rotl(self, n: u32) -> Self;
+	/// `self ^ (!b & c)`, the chi step.
+	fn chi(self, b: Self, c: Self) -> Self;
+}
+
+
+impl Lanes<1> for u64 {
+	#[inline(always)]
+	fn splat(lane: u64) -> u64 { lane }
+	#[inline(always)]
+	fn load(lanes: [u64; 1]) -> u64 { lanes[0] }
+	#[inline(always)]
+	fn store(self) -> [u64; 1] { [self] }
+	#[inline(always)]
+	fn xor(self, other: u64) -> u64 { self ^ other }
+	#[inline(always)]
+	fn rotl(self, n: u32) -> u64 { self.rotate_left(n) }
+	#[inline(always)]
+	fn chi(self, b: u64, c: u64) -> u64 { self ^ (!b & c) }
+}
+
+
+impl<const N: usize> Lanes<N> for [u64; N] {
+	#[inline(always)]
+	fn splat(lane: u64) -> [u64; N] { [lane; N] }
+	#[inline(always)]
+	fn load(lanes: [u64; N]) -> [u64; N] { lanes }
+	#[inline(always)]
+	fn store(self) -> [u64; N] { self }
+	#[inline(always)]
+	fn xor(self, other: [u64; N]) -> [u64; N] { std::array::from_fn(|n| self[n] ^ other[n]) }
+	#[inline(always)]
+	fn rotl(self, r: u32) -> [u64; N] { self.map(|lane| lane.rotate_left(r)) }
+	#[inline(always)]
+	fn chi(self, b: [u64; N], c: [u64; N]) -> [u64; N] { std::array::from_fn(|n| self[n] ^ (!b[n] & c[n])) }
+}
+
+
+/// The theta, rho and pi steps of a round.
+#[inline(always)]
+fn theta_rho_pi<V: Lanes<N>, const N: usize>(a: &mut [V; 25]) {
+	// Theta
+	let mut c: [V; 5] = [V::splat(0); 5];
+	for x in 0..5 {
+		c[x] = a[x].xor(a[x + 5]).xor(a[x + 10]).xor(a[x + 15]).xor(a[x + 20]);
+	}
+	for x in 0..5 {
+		let d: V = c[(x + 4) % 5].xor(c[(x + 1) % 5].rotl(1));
+		for y in (0..25).step_by(5) {
+			a[y + x] = a[y + x].xor(d);
+		}
+	}
+
+	// Rho and pi, unrolled so that every rotation is by a constant
+	macro_rules! rho_pi {
+		($($i:literal)*) => {
+			let mut last: V = a[1];
+			$(
+				let tmp: V = a[PI[$i]];
+				a[PI[$i]]  = last.rotl(RHO[$i]);
+				last       = tmp;
+			)*
+			let _ = last;
+		};
+	}
+	rho_pi!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23);
+}
+
+
+/// The chi and iota steps of a round.
+#[inline(always)]
+fn chi_iota<V: Lanes<N>, const N: usize>(a: &mut [V; 25], rc: u64) {
+	// Chi
+	for y in (0..25).step_by(5) {
+		let row: [V; 5] = [a[y], a[y + 1], a[y + 2], a[y + 3], a[y + 4]];
+		for x in 0..5 {
+			a[y + x] = row[x].chi(row[(x + 1) % 5], row[(x + 2) % 5]);
+		}
+	}
+
+	// Iota
+	a[0] = a[0].xor(V::splat(rc));
+}
+
+
+/// The keccak-f[1600] permutation, the lane `(x, y)` of the state is `a[x + 5*y]`.
+///
+/// Arguments:
+///
+/// * `a`: The state, permuted in place.
+#[inline]
+pub fn keccak_f1600(a: &mut [u64; 25]) {
+	for rc in RC {
+		theta_rho_pi(a);
+		chi_iota(a, rc);
+	}
+}
+
+
+/// Number of independent states hashed together by the `Portable` backend, whatever the target, see the
+/// `Build` section of the README for its measure.
+pub const LANES: usize = 8;
+
+
+/// The keccak-f[1600] permutation of `N` independent states, laid out lane-interleaved : `a[i][n]` is
+/// the lane `i` of the state `n`, so that the compiler can vectorise each step across the states.
+///
+/// Arguments:
+///
+/// * `a`: The states, permuted in place.
+#[inline]
+pub fn keccak_f1600_x<const N: usize>(a: &mut [[u64; N]; 25]) {
+	for rc in RC {
+		theta_rho_pi(a);
+		chi_iota(a, rc);
+	}
+}
+
+
+/// The largest number of candidates hashed at once, by any backend.
+pub const MAX_LANES: usize = 8;
+
+
+/// The `Backend` enum lists the implementations of the selector hash of a search.
+///
+/// * `Scalar`: One candidate at a time, the fallback.
+/// * `Portable`: `LANES` candidates at a time, in arrays vectorised by the compiler for the build target.
+/// * `Avx2`: 4 candidates at a time, in AVX2 registers.
+/// * `Avx512`: 8 candidates at a time, in AVX-512 registers.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum Backend {
+	Scalar,
+	Portable,
+	Avx2,
+	Avx512,
+}
+
+
+impl Backend {
+
+	/// Every backend, the fastest last.
+	pub const ALL: [Backend; 4] = [Backend::Scalar, Backend::Portable, Backend::Avx2, Backend::Avx512];
+
+
+	/// The fastest backend available on this CPU, detected at runtime.
+	pub fn detect() -> Backend {
+		Backend::available().pop().unwrap_or(Backend::Scalar)
+	}
+
+
+	/// The backends available on this CPU, the fastest last.
+	pub fn available() -> Vec<Backend> {
+		Backend::ALL.into_iter().filter(|backend| backend.is_available()).collect()
+	}
+
+
+	/// Checks that the CPU supports the backend, `Scalar` and `Portable` always do.
+	pub fn is_available(self) -> bool {
+		match self {
+			Backend::Scalar   => true,
+			Backend::Portable => true,
+			#[cfg(target_arch = "x86_64")]
+			Backend::Avx2     => is_x86_feature_detected!("avx2"),
+			#[cfg(target_arch = "x86_64")]
+			Backend::Avx512   => is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512vl"),
+			#[cfg(not(target_arch = "x86_64"))]
+			_                 => false,
+		}
+	}
+
+
+	/// The number of candidates hashed at once.
+	pub fn lanes(self) -> usize {
+		match self {
+			Backend::Scalar   => 1,
+			Backend::Portable => LANES,
+			Backend::Avx2     => 4,
+			Backend::Avx512   => 8,
+		}
+	}
+
+
+	/// The name of the backend, as displayed.
+	pub fn name(self) -> &'static str {
+		match self {
+			Backend::Scalar   => "scalar",
+			Backend::Portable => "portable",
+			Backend::Avx2     => "avx2",
+			Backend::Avx512   => "avx512",
+		}
+	}
+
+}
+
+
+impl fmt::Display for Backend {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} ({} lane(s))", self.name(), self.lanes())
+	}
+}
+
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+	use std::arch::x86_64::*;
+
+	use super::{Lanes, Template};
+
+	// The intrinsics are only reached through `avx2` and `avx512`, which are only called when the CPU
+	// has the features, see `Backend::is_available`.
+
+	impl Lanes<4> for __m256i {
+		#[inline(always)]
+		fn splat(lane: u64) -> __m256i { unsafe { _mm256_set1_epi64x(lane as i64) } }
+		#[inline(always)]
+		fn load(lanes: [u64; 4]) -> __m256i { unsafe { _mm256_loadu_si256(lanes.as_ptr() as *const __m256i) } }
+		#[inline(always)]
+		fn store(self) -> [u64; 4] {
+			let mut lanes: [u64; 4] = [0; 4];
+			unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, self) };
+			lanes
+		}
+		#[inline(always)]
+		fn xor(self, other: __m256i) -> __m256i { unsafe { _mm256_xor_si256(self, other) } }
+		#[inline(always)]
+		fn rotl(self, n: u32) -> __m256i {
+			unsafe {
+				_mm256_or_si256(
+					_mm256_sllv_epi64(self, _mm256_set1_epi64x(n as i64)),
+					_mm256_srlv_epi64(self, _mm256_set1_epi64x(64 - n as i64)),
+				)
+			}
+		}
+		#[inline(always)]
+		fn chi(self, b: __m256i, c: __m256i) -> __m256i { unsafe { _mm256_xor_si256(self, _mm256_andnot_si256(b, c)) } }
+	}
+
+	impl Lanes<8> for __m512i {
+		#[inline(always)]
+		fn splat(lane: u64) -> __m512i { unsafe { _mm512_set1_epi64(lane as i64) } }
+		#[inline(always)]
+		fn load(lanes: [u64; 8]) -> __m512i { unsafe { _mm512_loadu_si512(lanes.as_ptr() as *const __m512i) } }
+		#[inline(always)]
+		fn store(self) -> [u64; 8] {
+			let mut lanes: [u64; 8] = [0; 8];
+			unsafe { _mm512_storeu_si512(lanes.as_mut_ptr() as *mut __m512i, self) };
+			lanes
+		}
+		#[inline(always)]
+		fn xor(self, other: __m512i) -> __m512i { unsafe { _mm512_xor_si512(self, other) } }
+		#[inline(always)]
+		fn rotl(self, n: u32) -> __m512i { unsafe { _mm512_rolv_epi64(self, _mm512_set1_epi64(n as i64)) } }
+		// 0xd2 is the truth table of `a ^ (!b & c)`
+		#[inline(always)]
+		fn chi(self, b: __m512i, c: __m512i) -> __m512i { unsafe { _mm512_ternarylogic_epi64::<0xd2>(self, b, c) } }
+	}
+
+	#[target_feature(enable = "avx2")]
+	pub(super) unsafe fn avx2(template: &Template, suffixes: [&[u8]; 4]) -> [u32; 4] {
+		template.hash::<__m256i, 4>(suffixes)
+	}
+
+	#[target_feature(enable = "avx512f,avx512vl")]
+	pub(super) unsafe fn avx512(template: &Template, suffixes: [&[u8]; 8]) -> [u32; 8] {
+		template.hash::<__m512i, 8>(suffixes)
+	}
+}
+
+
+/// For each lane after the rho and pi steps, the lane it comes from and its rotation.
+const fn rho_pi_sources() -> ([usize; 25], [u32; 25]) {
+	let mut src: [usize; 25] = [0; 25];
+	let mut rot: [u32; 25]   = [0; 25];
+	let mut from: usize      = 1;
+	let mut i: usize         = 0;
+	while i < 24 {
+		src[PI[i]] = from;
+		rot[PI[i]] = RHO[i];
+		from       = PI[i];
+		i += 1;
+	}
+	(src, rot)
+}
+
+const RHO_PI_SRC: [usize; 25] = rho_pi_sources().0;
+const RHO_PI_ROT: [u32; 25]   = rho_pi_sources().1;
+
+
+/// Xors a block of `RATE` bytes into the first lanes of the state.
+#[inline]
+fn absorb(state: &mut [u64; 25], block: &[u8; RATE]) {
+	for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+		*lane ^= u64::from_le_bytes(bytes.try_into().expect("8 bytes"));
+	}
+}
+
+
+/// Computes the selector of a message, the first 4 bytes of its Keccak-256 hash as a big endian `u32`.
+///
+/// Arguments:
+///
+/// * `message`: The message to hash, usually a function signature.
+///
+/// Returns:
+///
+/// The selector, e.g. `0xa0712d68` for `mint(uint256)`.
+#[inline]
+pub fn selector(message: &[u8]) -> u32 {
+	let mut state: [u64; 25] = [0; 25];
+	let mut block: [u8; RATE] = [0; RATE];
+
+	// Full blocks, only for very long signatures
+	let mut chunks = message.chunks_exact(RATE);
+	for chunk in &mut chunks {
+		block.copy_from_slice(chunk);
+		absorb(&mut state, &block);
+		keccak_f1600(&mut state);
+	}
+
+	// Last block, padded in place (Keccak padding, not the SHA-3 one)
+	let rest: &[u8] = chunks.remainder();
+	block = [0; RATE];
+	block[..rest.len()].copy_from_slice(rest);
+	block[rest.len()] ^= 0x01;
+	block[RATE - 1]   ^= 0x80;
+	absorb(&mut state, &block);
+	keccak_f1600(&mut state);
+
+	// The first 4 bytes of the hash are the low bytes of the first lane
+	(state[0] as u32).swap_bytes()
+}
+
+
+/// The `Template` struct holds the padded block of the messages `prefix + suffix + postfix` sharing the
+/// same prefix, postfix and suffix length, i.e. all the candidates of a pass.
+///
+/// The lanes that don't depend on the suffix, and their share of the first round theta, rho and pi
+/// steps (all linear), are computed once. Each candidate then only fills the lanes covered by its suffix.
+///
+/// Properties:
+///
+/// * `message`: The message with a zeroed suffix, used when it doesn't fit in a single block.
+/// * `first`: The constant lanes of the block after the theta, rho and pi steps of the first round.
+/// * `suffix_at`: The offset of the suffix in the message.
+/// * `suffix_len`: The length of the suffix.
+/// * `single`: The padded message fits in a single block.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Template {
+	message   : Vec<u8>,
+	first     : [u64; 25],
+	suffix_at : usize,
+	suffix_len: usize,
+	single    : bool,
+}
+
+
+impl Template {
+
+	/// Builds the template of the messages `prefix + suffix + postfix`, for suffixes of `suffix_len` bytes.
+	pub fn new(prefix: &[u8], suffix_len: usize, postfix: &[u8]) -> Template {
+		let mut message: Vec<u8> = Vec::with_capacity(prefix.len() + suffix_len + postfix.len());
+		message.extend_from_slice(prefix);
+		message.resize(prefix.len() + suffix_len, 0);
+		message.extend_from_slice(postfix);
+
+		let single: bool         = message.len() < RATE;
+		let mut first: [u64; 25] = [0; 25];
+		if single {
+			let mut block: [u8; RATE] = [0; RATE];
+			block[..message.len()].copy_from_slice(&message);
+			block[message.len()] ^= 0x01;
+			block[RATE - 1]      ^= 0x80;
+			absorb(&mut first, &block);
+			theta_rho_pi(&mut first);
+		}
+
+		Template {
+			message,
+			first,
+			suffix_at : prefix.len(),
+			suffix_len,
+			single,
+		}
+	}
+
+
+	/// The length of the suffixes of the template.
+	pub fn suffix_len(&self) -> usize {
+		self.suffix_len
+	}
+
+
+	/// Computes the selector of the message built with `suffix`.
+	///
+	/// Arguments:
+	///
+	/// * `suffix`: The suffix, `suffix_len` bytes.
+	///
+	/// Returns:
+	///
+	/// The selector, the same as `selector(prefix + suffix + postfix)`.
+	#[inline]
+	pub fn selector(&self, suffix: &[u8]) -> u32 {
+		debug_assert_eq!(suffix.len(), self.suffix_len);
+		if !self.single {
+			let mut message: Vec<u8> = self.message.clone();
+			message[self.suffix_at..self.suffix_at + self.suffix_len].copy_from_slice(suffix);
+			return selector(&message);
+		}
+
+		self.hash::<u64, 1>([suffix])[0]
+	}
+
+
+	/// Computes the selectors of the messages built with `N` suffixes at once, see `keccak_f1600_x`.
+	///
+	/// Arguments:
+	///
+	/// * `suffixes`: The suffixes, `suffix_len` bytes each.
+	///
+	/// Returns:
+	///
+	/// The selectors, in the same order as the suffixes.
+	#[inline]
+	pub fn selectors<const N: usize>(&self, suffixes: [&[u8]; N]) -> [u32; N] {
+		if !self.single {
+			return suffixes.map(|suffix| self.selector(suffix));
+		}
+
+		self.hash::<[u64; N], N>(suffixes)
+	}
+
+
+	/// Computes the selectors of the messages built with `suffixes`, `backend.lanes()` at a time.
+	///
+	/// Arguments:
+	///
+	/// * `backend`: The `Backend` to hash with, it must be available on this CPU.
+	/// * `suffixes`: The suffixes, `suffix_len` bytes each.
+	/// * `selectors`: Filled with the selectors, in the same order as the suffixes.
+	#[inline]
+	pub fn selectors_with(&self, backend: Backend, suffixes: &[&[u8]], selectors: &mut [u32]) {
+		assert_eq!(suffixes.len(), selectors.len());
+		debug_assert!(backend.is_available());
+
+		let lanes: usize = backend.lanes();
+		let mut chunks   = suffixes.chunks_exact(lanes);
+		let mut outputs  = selectors.chunks_exact_mut(lanes);
+		for (chunk, output) in (&mut chunks).zip(&mut outputs) {
+			if !self.single {
+				for (suffix, selector) in chunk.iter().zip(output.iter_mut()) {
+					*selector = self.selector(suffix);
+				}
+				continue;
+			}
+			match backend {
+				Backend::Scalar   => output[0] = self.hash::<u64, 1>([chunk[0]])[0],
+				Backend::Portable => output.copy_from_slice(&self.hash::<[u64; LANES], LANES>(chunk.try_into().expect("LANES suffixes"))),
+				#[cfg(target_arch = "x86_64")]
+				Backend::Avx2     => output.copy_from_slice(&unsafe { x86::avx2(self, chunk.try_into().expect("4 suffixes")) }),
+				#[cfg(target_arch = "x86_64")]
+				Backend::Avx512   => output.copy_from_slice(&unsafe { x86::avx512(self, chunk.try_into().expect("8 suffixes")) }),
+				#[cfg(not(target_arch = "x86_64"))]
+				_                 => unreachable!("{} backend on a non x86_64 CPU", backend.name()),
+			}
+		}
+
+		// The last partial batch
+		for (suffix, selector) in chunks.remainder().iter().zip(outputs.into_remainder()) {
+			*selector = self.selector(suffix);
+		}
+	}
+
+
+	/// Hashes `N` single block messages at once, in `V` lanes.
+	#[inline(always)]
+	fn hash<V: Lanes<N>, const N: usize>(&self, suffixes: [&[u8]; N]) -> [u32; N] {
+		// Lanes of the suffixes alone
+		let mut var: [[u64; N]; 25] = [[0; N]; 25];
+		for (n, suffix) in suffixes.iter().enumerate() {
+			debug_assert_eq!(suffix.len(), self.suffix_len);
+			for (k, &byte) in suffix.iter().enumerate() {
+				let at: usize = self.suffix_at + k;
+				var[at / 8][n] ^= (byte as u64) << (8 * (at % 8));
+			}
+		}
+		let var: [V; 25] = var.map(V::load);
+
+		// Their theta step, then their rho and pi steps added to the constant lanes
+		let mut c: [V; 5] = [V::splat(0); 5];
+		for x in 0..5 {
+			c[x] = var[x].xor(var[x + 5]).xor(var[x + 10]).xor(var[x + 15]);
+		}
+		let mut d: [V; 5] = [V::splat(0); 5];
+		for x in 0..5 {
+			d[x] = c[(x + 4) % 5].xor(c[(x + 1) % 5].rotl(1));
+		}
+
+		let mut state: [V; 25] = [V::splat(0); 25];
+		for j in 0..25 {
+			let from: usize = RHO_PI_SRC[j];
+			state[j] = V::splat(self.first[j]).xor(var[from].xor(d[from % 5]).rotl(RHO_PI_ROT[j]));
+		}
+		chi_iota(&mut state, RC[0]);
+
+		for &rc in &RC[1..] {
+			theta_rho_pi(&mut state);
+			chi_iota(&mut state, rc);
+		}
+
+		state[0].store().map(|lane| (lane as u32).swap_bytes())
+	}
+
+}
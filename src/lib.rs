@@ -0,0 +1,45 @@
+//! Select0r - Selector Optimizer, find better function name to optimize gas cost.
+//!
+//! The search engine behind the `select0r` binary. Build a [`Globals`] from a
+//! function signature, hand it to [`Searcher::new`] and call [`Searcher::run`]
+//! to get the list of [`SignatureResult`].
+//!
+//! ```no_run
+//! use select0r::{Globals, Searcher};
+//!
+//! let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+//! g.difficulty  = 2;
+//! g.max_results = 4;
+//!
+//! let searcher: Searcher = Searcher::new(g).unwrap();
+//! for res in searcher.run() {
+//!     println!("{:>08x}\t{}", res.selector, res.signature);
+//! }
+//! ```
+#![allow(clippy::doc_lazy_continuation)]
+
+pub mod alphabet;
+pub mod bench;
+pub mod checkpoint;
+pub mod flexible;
+pub mod globals;
+pub mod keccak;
+pub mod output;
+pub mod pattern;
+pub mod permutation;
+pub mod planner;
+pub mod search;
+pub mod selector;
+pub mod syllable;
+
+pub use alphabet::Alphabet;
+pub use checkpoint::Checkpoint;
+pub use flexible::FlexibleTypes;
+pub use globals::{ConfigError, Globals, IteratedValue, Placement};
+pub use keccak::Backend;
+pub use output::Output;
+pub use pattern::Pattern;
+pub use permutation::Permutation;
+pub use planner::Plan;
+pub use search::{Event, Progress, SearchReport, Searcher, StopReason};
+pub use selector::SignatureResult;
@@ -1,314 +1,35 @@
+#![allow(clippy::doc_lazy_continuation)]
+
 extern crate num_cpus;
-extern crate crypto;
 
-use std::io::prelude::*;
-use std::f64;
-use std::fs::File;
-use std::io::BufWriter; // *
 use std::env;
+use std::fs;
+use std::io::{IsTerminal, Write};
 use text_colorizer::*;
 
-use crypto::digest::Digest;
-use self::crypto::sha3::Sha3;
-
+use std::path::Path;
 use std::process;
-//use std::thread;
-use crossbeam::thread;
-use std::sync::Mutex;
-
-
-
-#[macro_use]
-extern crate lazy_static;
-lazy_static! {
-	static ref SHARED_RESULTS: Mutex<Vec<SignatureResult>> = Mutex::new(
-		Vec::new()
-	);
-}
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use select0r::bench::{self, BenchResult};
+use select0r::output;
+use select0r::planner::{self, Plan};
+use select0r::{Alphabet, Backend, Checkpoint, Event, FlexibleTypes, Globals, Output, Pattern, Permutation, Placement, Progress, SearchReport, Searcher};
 
-type  IteratedValue           = u32;	// u64;
-
-const BASE_NN: IteratedValue  = 64;
-const BASE_MAX: IteratedValue = BASE_NN-1;
-const BASE_BITS: u32          = BASE_MAX.count_ones();
 
 const LOW: &str   = "▦";
 const FOUND: &str = "■";
 const STAR: &str  = "★";
 
+const CHECKPOINT_EVERY: Duration = Duration::from_secs(60);
+const PROGRESS_EVERY: Duration   = Duration::from_secs(1);
 
-#[derive(Clone)]
-#[derive(Debug)]
-enum Output {
-	TSV,
-	CSV,
-	JSON,
-	XML,
-	RON,
-}
-
-
-//	#[derive(Debug)]
-// this is just going to allow us to use
-// the Standard output a little better
-// to kind of format
-//
-//	#[allow(dead_code)]
-// allow us to suppress warnings bind to dead code
-#[derive(Debug)]
-#[derive(Clone)]
-#[allow(dead_code)]
-struct Globals {
-	signature  : String,
-	part_name  : String,
-	part_args  : String,
-	difficulty : u32,
-	nn_threads : usize,
-	digit_max  : u32,
-	leading0   : bool,
-	results    : Vec<SignatureResult>,
-	max_results: usize,
-	output     : Output,
-}
-
-
-struct SelectorResult {
-	selector    : u32,
-	zero_counter: u32,
-}
-
-
-/// The `SignatureResult` struct represents the result of a signature operation, containing a signature
-/// string, a selector value, and a leading zero count.
-///
-/// Properties:
-///
-/// * `signature`: A string that represents a valid Solidity signature.
-/// * `selector`: The `selector` property is of type `u32`, which stands for unsigned 32-bit integer. It
-/// is used to store a numeric value that represents a selector.
-/// * `leading_zero`: The `leading_zero` property is of type `u32`, which stands for unsigned 32-bit
-/// integer. It represents the number of leading zeros in the binary representation of the `signature`
-/// property.
-#[derive(Clone)]
-#[derive(Debug)]
-struct SignatureResult {
-	signature   : String,
-	selector    : u32,
-	leading_zero: u32,
-	nbr_of_zero : u32,
-}
-
-
-/// The function `base64_to_string` converts a given digit and value into a string using a specific
-/// alphabet.
-///
-/// Arguments:
-///
-/// * `digit`: The `digit` parameter represents the number of digits in the base64 value that you want
-/// to convert to a string.
-/// * `value`: The `value` parameter in the `base64_to_string` function is of type `IteratedValue`. It
-/// represents the value that needs to be converted from base64 to a string.
-///
-/// Returns:
-///
-/// The function `base64_to_string` returns a `String` as we know that it's a valid UTF-8 string.
-fn base64_to_string(digit: u32, mut value: IteratedValue) -> String {
-	const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
-
-	let mut buffer: Vec<u8> = vec![0u8; digit as usize];
-
-	for i in (0..digit as usize).rev() {
-		buffer[i] =   ALPHABET[(value & BASE_MAX) as usize];
-		value     >>= BASE_BITS;
-	}
-
-	// Direct conversion (we know that ALPHABET is UTF-8 valid)
-	unsafe { String::from_utf8_unchecked(buffer) }
-}
-
-
-/// The function takes a signature as input, hashes it using SHA3, and converts the resulting hash into
-/// a selector by counting the number of leading zeros and converting the first 4 bytes into a u32
-/// value.
-///
-/// Arguments:
-///
-/// * `signature`: The `signature` parameter is a string that represents a function signature. It is
-/// used to generate a selector, which is a unique identifier for the function.
-/// * `hasher`: The `hasher` parameter is an instance of the `Sha3` struct, which is used to compute the
-/// SHA-3 hash of the input signature. It is passed as a mutable reference to the function so that it
-/// can be reset and reused for multiple computations.
-///
-/// Returns:
-///
-/// The function `signature_to_selector` returns a `SelectorResult` struct.
-fn signature_to_selector(signature: &str, mut hasher: Sha3) -> SelectorResult {
-
-	hasher.reset();
-	hasher.input_str(signature);
-	let mut selector_u8_vec: [u8; 32] = [0; 32];
-	hasher.result(&mut selector_u8_vec);
-
-	let (zero_counter, selector_u32) = selector_u8_vec
-		.iter()
-		.take(4)
-		.enumerate()
-		.fold((0, 0), |(zero_counter, selector_u32), (_i, &vu8)| {(
-			if vu8 == 0 { zero_counter + 1 } else { zero_counter },
-			(selector_u32 << 8) + (vu8 as u32),
-		)});
-
-	SelectorResult {
-		selector    : selector_u32,
-		zero_counter: zero_counter,
-	}
-}
-
-
-/// The function counts the number of leading zeros in a 32-bit unsigned integer.
-///
-/// Arguments:
-///
-/// * `selector_u32`: The parameter `selector_u32` is an unsigned 32-bit integer.
-///
-/// Returns:
-///
-/// If none of the conditions in the if statements are true, then the function will return 4.
-fn count_leading_zeros(selector_u32: u32) -> u32 {
-	if (selector_u32 & 0xFF000000) != 0 { return 0;}
-	if (selector_u32 & 0x00FF0000) != 0 { return 1;}
-	if (selector_u32 & 0x0000FF00) != 0 { return 2;}
-	if (selector_u32 & 0x000000FF) != 0 { return 3;}
-	4
-}
-
-
-/// The function takes in some parameters, computes a signature result based on those parameters, and
-/// returns it as an option.
-///
-/// Arguments:
-///
-/// * `g`: A reference to a struct called `Globals` which contains global variables and settings for the
-/// computation.
-/// * `digit`: The `digit` parameter is of type `u32` and represents the number of base 64 digits used in the
-/// computation.
-/// * `value`: The `value` parameter is of type `IteratedValue`. It represents some value that has been
-/// iterated over.
-/// * `hasher`: The `hasher` parameter is of type `Sha3`, which is a hash function. It is used to
-/// compute the hash value of the `signature` string.
-///
-/// Returns:
-///
-/// The function `compute` returns an `Option<SignatureResult>`.
-fn compute(g: &Globals, digit: u32, value: IteratedValue, hasher: Sha3) -> Option<SignatureResult> {
-	let value64: String     = base64_to_string(digit, value);
-	let signature: String   = format!("{}_{}{}",g.part_name ,value64, g.part_args );
-	let s2s: SelectorResult = signature_to_selector(&signature, hasher);
-	let selector_u32: u32   = s2s.selector;
-	let zero_counter: u32   = s2s.zero_counter;
-
-	//if selector_u32 == 0 {return None;}
-	if zero_counter < g.difficulty {return None;}
-
-	//println!("{:>8x}\t{}\t{:?}", selector_u32, signature, &selector_u8_vec[..4]);
-	let leading_zero = count_leading_zeros(selector_u32);
-
-	Some( SignatureResult {
-		signature   : signature,
-		selector    : selector_u32,
-		leading_zero: leading_zero,
-		nbr_of_zero : zero_counter,
-	})
-
-}
-
+const BENCH_HASHES: u64           = 2_000_000;
+const BENCH_FILE: &str            = "select0r-bench.json";
 
-/// The function `thread` takes in some parameters and performs computations using a hashing algorithm,
-/// updating shared variables and printing progress along the way.
-///
-/// Arguments:
-///
-/// * `g`: The parameter `g` is of type `Globals` and represents a struct that contains global variables
-/// and settings for the program.
-/// * `idx`: The `idx` parameter represents the starting index for the iteration. It is used to
-/// determine the range of values that the `for_each` loop will iterate over.
-/// * `digit`: The `digit` parameter is of type `u32` and represents the number of base 64 digits.
-/// * `max`: The `max` parameter represents the maximum value for the iteration. It is of type
-/// `IteratedValue`.
-fn thread(g: Globals, idx: IteratedValue, digit: u32, max: IteratedValue) {
-	let hasher: crypto::sha3::Sha3 = crypto::sha3::Sha3::keccak256();
-	let mut optimal:u32       = u32::MAX;
-	let mut nn_results: usize = 1;
-	{
-		let shared: std::sync::MutexGuard<'_, Vec<SignatureResult>> = SHARED_RESULTS.lock().expect("Mutex panic ! ");
-		if let Some(last_signature) = shared.last() {
-			optimal    = last_signature.selector;
-			nn_results = shared.len();
-		}
-	}
-
-	(idx..max).step_by(g.nn_threads).for_each( |value| {
-		match compute(&g, digit, value, hasher) {
-			None => {},
-			Some(s) => {
-
-				if g.leading0 {
-					if s.selector < optimal {
-						optimal = s.selector;
-						{
-							let mut shared: std::sync::MutexGuard<'_, Vec<SignatureResult>> = SHARED_RESULTS.lock().expect("Mutex panic ! ");
-							if let Some(last_signature) = shared.last() {
-
-								nn_results = shared.len();
-
-								let shared_optimal: u32 = last_signature.selector;
-
-								match shared_optimal.cmp(&optimal) {
-									std::cmp::Ordering::Less => {
-										optimal = shared_optimal;
-									}
-									std::cmp::Ordering::Greater => {
-										print!("{}", in_progress(s.leading_zero));
-										shared.push( SignatureResult {
-											signature   : s.signature,
-											selector    : optimal,
-											leading_zero: s.leading_zero,
-											nbr_of_zero : s.nbr_of_zero,
-										});
-										nn_results += 1;
-									}
-									_ => {}
-								}
-
-
-							}// if let Some(last_signature)
-						}// SHARED_RESULTS.lock()
-					}
-				} else {
-					//println!("  [{:>08X}]\t{}", s.selector, s.signature);
-					print!("{}", in_progress(s.leading_zero));
-					let mut shared: std::sync::MutexGuard<'_, Vec<SignatureResult>> = SHARED_RESULTS.lock().expect("Mutex panic ! ");
-					shared.push( SignatureResult{
-						signature   : s.signature,
-						selector    : s.selector,
-						leading_zero: s.leading_zero,
-						nbr_of_zero : s.nbr_of_zero,
-					});
-					nn_results = shared.len();
-				}
-
-				if (optimal == 0) || (nn_results >= g.max_results) {
-					write_file(&g, "Goal reached !");
-					process::exit(0);
-					//return;
-				}
-
-			}// Some()
-		};// match compute()
-	});// step_by(g.nn_threads).for_each(value)
-
-}
+const PLAN_MEASURE: Duration      = Duration::from_millis(500);
 
 
 /// The function `in_progress` takes an input `nn_zeros` and returns a colored string based on its
@@ -333,157 +54,178 @@ fn in_progress(nn_zeros: u32) -> ColoredString {
 }
 
 
-/// Launches multiple threads to perform operations based on the provided Global configuration.
+/// The `Screen` struct holds the lines displayed while searching : the line of the current pass with
+/// the glyphs of its results and, on a terminal, a status line below it, both redrawn in place.
 ///
-/// This function pushes a `SignatureResult` into the shared results,
-/// launches multiple threads for each pass based on number of base64 digit
-/// and performs operations based on the Global configuration and specified digits.
-///
-/// Arguments:
+/// Properties:
 ///
-/// * `g`: The parameter `g` is of type `&Globals`, which means it is a reference to an object of type
-/// `Globals`.
-fn threads_launcher(g: &Globals) -> &Globals {
-	{
-		let mut shared: std::sync::MutexGuard<'_, Vec<SignatureResult>> = SHARED_RESULTS.lock().unwrap();
-
-		let hasher: crypto::sha3::Sha3 = crypto::sha3::Sha3::keccak256();
-		let s2s: SelectorResult        = signature_to_selector(&g.signature, hasher);
-
-		shared.push(SignatureResult {
-			signature   : g.signature.clone(),
-			selector    : s2s.selector,
-			leading_zero: count_leading_zeros(s2s.selector),
-			nbr_of_zero : s2s.zero_counter,
-		});
-	}
-
-	(1..=g.digit_max).for_each( |digit| {
-		print!("Pass #{} ", digit);
-		let max: IteratedValue = 1 << (BASE_BITS*digit);
-
-		let _ = thread::scope(|scope| {
-			(0..g.nn_threads).for_each(|thread_idx| {
-				scope.spawn(move |_| {
-					thread( g.clone(), thread_idx as IteratedValue, digit, max);
-				});
-			});
-		});
+/// * `tty`: The standard output is a terminal, else only the pass lines are displayed, as they come.
+/// * `pass`: The line of the current pass, empty before the first one.
+/// * `status`: The status line, see `status`.
+/// * `drawn`: The lines are on the screen, the cursor at the end of the status line.
+struct Screen {
+	tty   : bool,
+	pass  : String,
+	status: String,
+	drawn : bool,
+}
 
-		println!();
 
-	});// for_each( digit)
-	println!("\n");
-	g
+impl Screen {
 
-}
+	fn new() -> Screen {
+		Screen {
+			tty   : std::io::stdout().is_terminal(),
+			pass  : String::new(),
+			status: String::new(),
+			drawn : false,
+		}
+	}
 
 
-/// The function `write_file` takes in a `Globals` struct and a message string, creates a file name
-/// based on the struct's properties, and writes the message and the struct's data to a file in the
-/// specified format.
-///
-/// Arguments:
-///
-/// * `g`: A reference to a struct called `Globals` which contains various configuration parameters for
-/// the file writing process.
-/// * `message`: A message to be display in standard output, before writing to the file.
-fn write_file(g: &Globals, message: &str) {
-	let file_name: String = format!("select0r-{}--zero={}-max={}-lead={}-cpu={}.{:?}",
-		g.signature, g.difficulty, g.max_results, g.leading0, g.nn_threads, g.output).to_lowercase();
+	/// Draws the pass line and the status line over the previous ones.
+	fn redraw(&mut self) {
+		let erase: &str = if self.drawn { "\r\x1b[2K\x1b[1A\r\x1b[2K" } else { "" };
+		print!("{}{}\n{}", erase, self.pass, self.status);
+		let _ = std::io::stdout().flush();
+		self.drawn = true;
+	}
 
-	println!("\n\n{}", message.green());
-	println!("Output : {}\n", file_name.cyan());
 
-	// Formater factory
-	let formatter: Box<dyn Formatter> = match g.output {
-		Output::TSV  => Box::new(TsvFormatter),
-		Output::CSV  => Box::new(CsvFormatter),
-		Output::JSON => Box::new(JsonFormatter),
-		Output::XML  => Box::new(XmlFormatter),
-		Output::RON  => Box::new(RonFormatter),
-	};
+	/// Ends the current pass line, the status line is erased.
+	fn end_pass(&mut self) {
+		if self.drawn {
+			println!("\r\x1b[2K\x1b[1A\r\x1b[2K{}", self.pass);
+			self.drawn = false;
+		} else if !self.tty && !self.pass.is_empty() {
+			println!();
+		}
+	}
+
+
+	/// Starts the line of a pass, the passes overlap so the line of the previous one ends there.
+	fn pass_start(&mut self, digit: u32) {
+		self.end_pass();
+		self.pass = format!("Pass #{} ", digit);
+		if self.tty {
+			self.redraw();
+		} else {
+			print!("{}", self.pass);
+			let _ = std::io::stdout().flush();
+		}
+	}
+
 
-	let file: File                  = File::create(&file_name).expect("Failed to create file");
-	let mut writer: BufWriter<File> = BufWriter::new(file);
+	/// Adds the glyph of a result to the pass line.
+	fn found(&mut self, glyph: ColoredString) {
+		self.pass.push_str(&glyph.to_string());
+		if self.tty {
+			self.redraw();
+		} else {
+			print!("{}", glyph);
+			let _ = std::io::stdout().flush();
+		}
+	}
 
-	// Write header
-	writer.write_all(formatter.header().as_bytes()).ok();
 
-	// Write lines of data
-	{
-		let shared: std::sync::MutexGuard<'_, Vec<SignatureResult>> = SHARED_RESULTS.lock().expect("Mutex panic !");
-		for (i, res) in shared.iter().enumerate() {
-			let line: String = formatter.line(res, i == 0);
-			writer.write_all(line.as_bytes()).ok();
+	/// Replaces the status line, only on a terminal.
+	fn progress(&mut self, progress: &Progress) {
+		if self.tty {
+			self.status = status(progress);
+			self.redraw();
 		}
 	}
 
-	// Write footer
-	writer.write_all(formatter.footer().as_bytes()).ok();
 }
 
-trait Formatter {
-	fn header(&self) -> String;
-	fn line(&self, res: &SignatureResult, is_first: bool) -> String;
-	fn footer(&self) -> String;
+
+/// The status line : hashes, hash rate, progress of the current pass and expected time to the next result.
+fn status(progress: &Progress) -> String {
+	let next_hit: String = match progress.next_hit {
+		Some(next_hit) => format!("~{}", human_duration(next_hit)),
+		None           => "?".to_string(),
+	};
+	format!("{} hashes | {} hashes/s | pass #{} {:.1}% | next hit {}",
+		human_count(progress.hashes as f64), human_count(progress.hashes_per_sec), progress.pass,
+		progress.pass_done * 100.0, next_hit).bright_black().to_string()
 }
 
-// Exemple d'Implementation for JSON
-struct JsonFormatter;
-impl Formatter for JsonFormatter {
-	fn header(&self) -> String { "{\"select0r\":[\n".to_string() }
-	fn footer(&self) -> String { "]}\n".to_string() }
-	fn line(&self, res: &SignatureResult, is_first: bool) -> String {
-		let comma: &str = if is_first { " " } else { "," };
-		format!("\t{}{{ \"selector\":\"{:>08x}\", \"nbr_of_zero\":{}, \"leading_zero\":{}, \"signature\":\"{}\" }}\n",
-			comma, res.selector, res.nbr_of_zero, res.leading_zero, res.signature)
+
+/// Formats a count with a unit prefix, e.g. `12.35 M`.
+fn human_count(count: f64) -> String {
+	match count {
+		c if c >= 1e18 => format!("{:.2} E", c / 1e18),
+		c if c >= 1e15 => format!("{:.2} P", c / 1e15),
+		c if c >= 1e12 => format!("{:.2} T", c / 1e12),
+		c if c >= 1e9  => format!("{:.2} G", c / 1e9),
+		c if c >= 1e6  => format!("{:.2} M", c / 1e6),
+		c if c >= 1e3  => format!("{:.2} k", c / 1e3),
+		c              => format!("{:.0}", c),
 	}
 }
 
-// Implementation for TSV
-struct TsvFormatter;
-impl Formatter for TsvFormatter {
-	fn header(&self) -> String { "SELECTOR\tNBR_OF_ZERO\tLEADING_ZERO\tSIGNATURE\n".to_string() }
-	fn footer(&self) -> String { "".to_string() }
-	fn line(&self, res: &SignatureResult, _is_first: bool) -> String {
-		format!("{:>08x}\t{}\t{}\t{}\n",
-			res.selector, res.nbr_of_zero, res.leading_zero, res.signature)
+
+/// Formats a duration with its two largest units, e.g. `2h 05m`.
+fn human_duration(duration: Duration) -> String {
+	let secs: u64 = duration.as_secs();
+	match secs {
+		0..=59           => format!("{}s", secs),
+		60..=3599        => format!("{}m {:02}s", secs / 60, secs % 60),
+		3600..=86399     => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+		86400..=31535999 => format!("{}d {:02}h", secs / 86400, secs % 86400 / 3600),
+		_                => format!("{}y {:03}d", secs / 31536000, secs % 31536000 / 86400),
 	}
 }
 
-// Implementation for CSV
-struct CsvFormatter;
-impl Formatter for CsvFormatter {
-	fn header(&self) -> String { "SELECTOR,NBR_OF_ZERO,LEADING_ZERO,SIGNATURE\n".to_string() }
-	fn footer(&self) -> String { "".to_string() }
-	fn line(&self, res: &SignatureResult, _is_first: bool) -> String {
-		format!("{:>08x},{},{},{}\n",
-			res.selector, res.nbr_of_zero, res.leading_zero, res.signature)
-	}
+
+/// The function `write_file` displays why the search stopped and the output file name, then writes the
+/// results to the file in the specified format.
+///
+/// Arguments:
+///
+/// * `g`: A reference to a struct called `Globals` which contains various configuration parameters for
+/// the file writing process.
+/// * `report`: The results to write, and why the search stopped.
+fn write_file(g: &Globals, report: &SearchReport) {
+	let file_name: String = output::file_name(g);
+
+	println!("\n\n{}", report.stop.to_string().green());
+	println!("Output : {}\n", file_name.cyan());
+
+	output::write_file(&file_name, g, &report.results, report.stop).expect("Failed to write file");
 }
 
-// Implementation for XML
-struct XmlFormatter;
-impl Formatter for XmlFormatter {
-	fn header(&self) -> String { "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<select0r>\n".to_string() }
-	fn footer(&self) -> String { "</select0r>\n".to_string() }
-	fn line(&self, res: &SignatureResult, _is_first: bool) -> String {
-		format!("\t<result>\n\t\t<selector>{:>08x}</selector>\n\t\t<nbr_of_zero>{}</nbr_of_zero>\n\t\t<leading_zero>{}</leading_zero>\n\t\t<signature>{}</signature>\n\t</result>\n",
-			res.selector, res.nbr_of_zero, res.leading_zero, res.signature)
-	}
+
+/// Parses a duration such as `90`, `90s`, `20m` or `2h`, in seconds when there is no unit.
+///
+/// Returns:
+///
+/// The `Duration`, `None` if the string is not a valid duration.
+fn parse_duration(arg: &str) -> Option<Duration> {
+	let (value, unit): (&str, u64) = match arg.chars().last()? {
+		's' => (&arg[..arg.len()-1], 1),
+		'm' => (&arg[..arg.len()-1], 60),
+		'h' => (&arg[..arg.len()-1], 3600),
+		_   => (arg, 1),
+	};
+	value.parse::<u64>().ok().map(|value| Duration::from_secs(value * unit))
 }
 
-// Implementation for RON
-struct RonFormatter;
-impl Formatter for RonFormatter {
-	fn header(&self) -> String { "Select0r( results: [\n".to_string() }
-	fn footer(&self) -> String { "],)\n".to_string() }
-	fn line(&self, res: &SignatureResult, is_first: bool) -> String {
-		let comma: &str = if is_first { " " } else { "," };
-		format!("\t{}(selector: \"{:>08x}\", nbr_of_zero: {}, leading_zero: {}, signature: \"{}\")\n",
-			comma, res.selector, res.nbr_of_zero, res.leading_zero, res.signature)
-	}
+
+/// Reads a word list, one word by line, the empty lines and those starting with `#` skipped.
+///
+/// Returns:
+///
+/// The name of the list, the name of the file without its extension, and its words.
+fn read_words(file_name: &str) -> std::io::Result<(String, Vec<String>)> {
+	let name: String       = Path::new(file_name).file_stem().map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+	let words: Vec<String> = fs::read_to_string(file_name)?
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(str::to_string)
+		.collect();
+	Ok((name, words))
 }
 
 
@@ -499,10 +241,13 @@ fn cli_help() {
 		"\n{} - Selector Optimizer, find better function name to optimize gas cost",
 		"Select0r".green().bold()
 	);
-	eprintln!("Usage : select0r s <function_signature string> z <number_of_zeros> r <max_results> d <decrement boolean> t <nbr_threads> o <format_ouput>");
+	eprintln!("Usage : select0r s <function_signature string> z <number_of_zeros> r <max_results> l <leading_zero boolean> t <nbr_threads> o <format_ouput> [d <deadline>] [h <max_hashes>] [k <stop_leading_zero>] [a <alphabet>] [g <pattern>] [w <word_list file>] [m <placement>] [j <separator>] [c <case_suffix>] [v <permuted_args>] [f <type_suffix>] [x <deterministic boolean>] [p <plan_only boolean>]");
+	eprintln!("        select0r resume <checkpoint_file> [d <deadline>] [h <max_hashes of this run>] [k <stop_leading_zero>]");
+	eprintln!("        select0r bench [s <function_signature string>] [h <hashes>] [t <max_threads>] [o json]");
 	eprintln!();
 	eprintln!("Example 1 : select0r s \"functionName(uint256)\"  z 2  r 5  l true  t 2  o tsv");
 	eprintln!("Example 2 : select0r s \"functionName2(uint)\"  z 2  r 7  l false  t 2  o json");
+	eprintln!("Example 3 : select0r s \"mint(uint256)\"  z 2  r 5  l false  t 2  o tsv  g \"{{syllable}}{{2,3}}\"");
 	eprintln!();
 }
 
@@ -512,10 +257,9 @@ fn cli_help() {
 ///
 /// Returns:
 ///
-/// The function `init_app()` returns a `Globals` struct.
-fn init_app() -> Globals {
-// TODO intercept ctrl-c to stop processing and write results on output file !
-
+/// The function `init_app()` returns a `Globals` struct, the `Checkpoint` to resume from if any, and
+/// whether only the plan of the search is wanted.
+fn init_app() -> (Globals, Option<Checkpoint>, bool) {
 	println!();
 	println!("  .--.--.               ,--,                          ___        ,----..             ");
 	println!(" /  /    '.           ,--.'|                        ,--.'|_     /   /   \\            ");
@@ -541,6 +285,20 @@ fn init_app() -> Globals {
 	let mut arg_leading0   : bool   = false;
 	let mut arg_threads    : usize  = 2;
 	let mut arg_output     : Output = Output::TSV;
+	let mut arg_resume     : String = "".to_string();
+	let mut arg_deadline   : Option<Duration> = None;
+	let mut arg_max_hashes : Option<u64>      = None;
+	let mut arg_stop_lead  : Option<u32>      = None;
+	let mut arg_alphabet   : Alphabet         = Alphabet::Base64;
+	let mut arg_pattern    : String           = "".to_string();
+	let mut arg_words      : Vec<(String, Vec<String>)> = vec![];
+	let mut arg_placement  : Placement        = Placement::Suffix;
+	let mut arg_separator  : String           = "_".to_string();
+	let mut arg_cases      : Option<u32>      = None;
+	let mut arg_permute    : Option<Vec<usize>> = None;
+	let mut arg_type_suffix: u32              = 0;
+	let mut arg_determ     : bool             = false;
+	let mut arg_plan       : bool             = false;
 
 	if (args.len() & 1) != 0 {
 		cli_help();
@@ -553,6 +311,7 @@ fn init_app() -> Globals {
 		process::exit(1);
 	}
 
+	#[allow(clippy::upper_case_acronyms)]
 	enum NextIs{
 		NOTHING,
 		SIGNATURE,
@@ -561,12 +320,27 @@ fn init_app() -> Globals {
 		LEADING0,
 		THREADS,
 		OUTPUT,
+		RESUME,
+		DEADLINE,
+		HASHES,
+		STOPLEAD,
+		ALPHABET,
+		PATTERN,
+		WORDS,
+		PLACEMENT,
+		SEPARATOR,
+		CASES,
+		PERMUTE,
+		TYPES,
+		ORDER,
+		PLAN,
 	}
 
 	let mut _next: NextIs = NextIs::NOTHING;
 
 	for arg in &args {
 		//println!("- {}", arg);
+		let is_value: bool = !matches!(_next, NextIs::NOTHING);
 		match _next {
 			NextIs::SIGNATURE => { arg_signature   = arg.to_string();},
 			NextIs::ZERO      => { arg_difficulty  = arg.parse::<u32>().expect("Invalid `z`parameter ! ").clamp(1,3);},
@@ -581,10 +355,33 @@ fn init_app() -> Globals {
 									"ron" |"RON"    => Output::RON,
 									_               => panic!("Invalid `o` parameter ! ")
 								};},
+			NextIs::RESUME    => { arg_resume      = arg.to_string();},
+			NextIs::DEADLINE  => { arg_deadline    = Some(parse_duration(arg).expect("Invalid `d` parameter ! "));},
+			NextIs::HASHES    => { arg_max_hashes  = Some(arg.parse::<u64>().expect("Invalid `h` parameter ! "));},
+			NextIs::STOPLEAD  => { arg_stop_lead   = Some(arg.parse::<u32>().expect("Invalid `k` parameter ! ").clamp(1,4));},
+			NextIs::ALPHABET  => { arg_alphabet    = arg.parse::<Alphabet>().unwrap_or_else(|e| panic!("Invalid `a` parameter, {} ! ", e));},
+			NextIs::PATTERN   => { arg_pattern     = arg.to_string();},
+			NextIs::WORDS     => { arg_words.push(read_words(arg).unwrap_or_else(|e| panic!("Invalid `w` parameter, {} ! ", e)));},
+			NextIs::PLACEMENT => { arg_placement   = match arg.as_str() {"suffix"|"SUFFIX"=>Placement::Suffix, "prefix"|"PREFIX"=>Placement::Prefix, _=>panic!("Invalid `m` parameter ! ")};},
+			NextIs::SEPARATOR => { arg_separator   = arg.to_string();},
+			NextIs::CASES     => { arg_cases       = Some(arg.parse::<u32>().expect("Invalid `c` parameter ! "));},
+			NextIs::PERMUTE   => { arg_permute     = Some(match arg.as_str() {
+									"all"|"ALL"|"" => vec![],
+									positions      => positions.split(',')
+										.map(|at| at.parse::<usize>().ok().and_then(|at| at.checked_sub(1)).expect("Invalid `v` parameter ! "))
+										.collect(),
+								});},
+			NextIs::TYPES     => { arg_type_suffix = arg.parse::<u32>().expect("Invalid `f` parameter ! ");},
+			NextIs::ORDER     => { arg_determ      = match arg.as_str() {"1"|"true"|"TRUE"=>true, "0"|"false"|"FALSE"=>false, _=>panic!("Invalid `x` parameter ! ")};},
+			NextIs::PLAN      => { arg_plan        = match arg.as_str() {"1"|"true"|"TRUE"=>true, "0"|"false"|"FALSE"=>false, _=>panic!("Invalid `p` parameter ! ")};},
 			_                 => {},
 		}
 		_next = NextIs::NOTHING;
 
+		// A value is never read as a key, e.g. the `x` of `j x`
+		if is_value {
+			continue;
+		}
 		match arg.as_str() {
 			"s"|"S" => { _next = NextIs::SIGNATURE;},
 			"z"|"Z" => { _next = NextIs::ZERO;},
@@ -592,11 +389,55 @@ fn init_app() -> Globals {
 			"l"|"L" => { _next = NextIs::LEADING0;},
 			"t"|"T" => { _next = NextIs::THREADS;},
 			"o"|"O" => { _next = NextIs::OUTPUT;},
+			"d"|"D" => { _next = NextIs::DEADLINE;},
+			"h"|"H" => { _next = NextIs::HASHES;},
+			"k"|"K" => { _next = NextIs::STOPLEAD;},
+			"a"|"A" => { _next = NextIs::ALPHABET;},
+			"g"|"G" => { _next = NextIs::PATTERN;},
+			"w"|"W" => { _next = NextIs::WORDS;},
+			"m"|"M" => { _next = NextIs::PLACEMENT;},
+			"j"|"J" => { _next = NextIs::SEPARATOR;},
+			"c"|"C" => { _next = NextIs::CASES;},
+			"v"|"V" => { _next = NextIs::PERMUTE;},
+			"f"|"F" => { _next = NextIs::TYPES;},
+			"x"|"X" => { _next = NextIs::ORDER;},
+			"p"|"P" => { _next = NextIs::PLAN;},
+			"resume"|"RESUME" => { _next = NextIs::RESUME;},
 			_       => { _next = NextIs::NOTHING;},
 		}
 
 	}
 
+	// The settings of a resumed search are those of its checkpoint
+	let checkpoint: Option<Checkpoint> = if arg_resume.is_empty() {
+		None
+	} else {
+		match Checkpoint::load(&arg_resume) {
+			Ok(cp) => {
+				arg_signature   = cp.signature.clone();
+				arg_difficulty  = cp.difficulty;
+				arg_max_results = cp.max_results as u32;
+				arg_leading0    = cp.leading0;
+				arg_determ      = cp.deterministic;
+				arg_alphabet    = Alphabet::from_chars(&cp.alphabet);
+				arg_pattern     = cp.pattern.clone();
+				arg_words       = cp.words.clone();
+				arg_placement   = cp.placement;
+				arg_separator   = cp.separator.clone();
+				arg_cases       = cp.cases;
+				arg_permute     = cp.permutation.clone();
+				arg_type_suffix = cp.type_suffix.unwrap_or(0);
+				arg_output      = cp.output.clone();
+				arg_threads     = cp.nn_threads;
+				Some(cp)
+			},
+			Err(e) => {
+				eprintln!("{} checkpoint `{}` : {}\n", "Error".red().bold(), arg_resume, e);
+				process::exit(1);
+			}
+		}
+	};
+
 	if arg_signature.is_empty() {
 		cli_help();
 		panic!("No signature !?");
@@ -607,37 +448,274 @@ fn init_app() -> Globals {
 	println!("- Difficulty\t{} zero(s)", arg_difficulty);
 	println!("- Max results\t{}",        arg_max_results);
 	println!("- Leading `0`\t{}",           arg_leading0);
-	println!("- Nbr threads\t{} CPU(s)", arg_threads);
+	println!("- Nbr threads\t{} CPU(s), {} backend", arg_threads, Backend::detect());
 	println!("- Output\t{:?} file",      arg_output);
+	if let Some(deadline) = arg_deadline {
+		println!("- Deadline\t{:?}",        deadline);
+	}
+	if let Some(max_hashes) = arg_max_hashes {
+		println!("- Max hashes\t{}",        max_hashes);
+	}
+	if let Some(stop_lead) = arg_stop_lead {
+		println!("- Stop at\t{} leading `0`", stop_lead);
+	}
+	let pattern: Option<Pattern> = if arg_pattern.is_empty() {
+		None
+	} else {
+		match Pattern::with_words(&arg_pattern, arg_words) {
+			Ok(pattern) => Some(pattern),
+			Err(e)      => {
+				cli_help();
+				eprintln!("{} {}\n", "Error".red().bold(), e);
+				process::exit(1);
+			}
+		}
+	};
+	if let Some(pattern) = &pattern {
+		println!("- Pattern\t{} ({} pass(es))", pattern, pattern.passes());
+		for (name, words) in pattern.words() {
+			println!("- Words\t\t{} ({} words)", name, words.len());
+		}
+	} else if arg_alphabet != Alphabet::Base64 {
+		println!("- Alphabet\t{} ({} chars)", arg_alphabet, arg_alphabet.len());
+	}
+	if arg_signature.contains("{}") {
+		println!("- Placement\tat the `{{}}` placeholder");
+	} else if arg_placement != Placement::Suffix || arg_separator != "_" {
+		println!("- Placement\t{}, separator `{}`", arg_placement.name(), arg_separator);
+	}
+	// The suffixes of the cases and the types have at most the passes of the pattern or the alphabet
+	let suffix_passes: u32 = pattern.as_ref().map_or(arg_alphabet.max_digits(), Pattern::passes);
+	if let Some(cases) = arg_cases {
+		println!("- Cases\t\tvariants of the name, suffix up to {} pass(es)", cases.min(suffix_passes));
+	}
+	match &arg_permute {
+		Some(positions) if positions.is_empty() => println!("- Permutation\tall the arguments"),
+		Some(positions)                         => println!("- Permutation\targuments {}", Permutation::describe(positions)),
+		None                                    => {},
+	}
+	if let Ok(types) = FlexibleTypes::new(&arg_signature[arg_signature.find('(').unwrap_or(0)..]) {
+		println!("- Types\t\t{} combination(s), suffix up to {} pass(es)", types.len(), arg_type_suffix.min(suffix_passes));
+	}
+	if arg_determ {
+		println!("- Order\t\tdeterministic");
+	}
+	if let Some(cp) = &checkpoint {
+		println!("- Resume\t`{}` (pass #{}, {} result(s))", arg_resume, cp.digit, cp.results.len());
+	}
 	println!();
 
-	let parenthesis: usize = arg_signature.find('(').expect("Valid Solidity signature (?) ");
-	let part_n: &str       = &arg_signature[..parenthesis];
-	let part_a: &str       = &arg_signature[parenthesis..];
-	let digit: u32         = (f64::log(IteratedValue::MAX as f64, BASE_NN as f64) as u32) + 1;
+	let mut g: Globals = match Globals::new(&arg_signature) {
+		Ok(g)  => g,
+		Err(e) => {
+			cli_help();
+			eprintln!("{} {}\n", "Error".red().bold(), e);
+			process::exit(1);
+		}
+	};
+	g.difficulty  = arg_difficulty;
+	g.nn_threads  = arg_threads;
+	g.leading0    = arg_leading0;
+	g.max_results = arg_max_results as usize;
+	g.output      = arg_output;
+	g.deterministic    = arg_determ;
+	g.placement        = arg_placement;
+	g.separator        = arg_separator;
+	g.set_alphabet(arg_alphabet);
+	if let Some(pattern) = pattern {
+		g.set_pattern(pattern);
+	}
+	if let Some(cases) = arg_cases {
+		g.set_cases(cases);
+	}
+	g.set_type_suffix(arg_type_suffix);
+	if let Some(positions) = arg_permute {
+		match Permutation::new(&g.part_args, &positions) {
+			Ok(permutation) => g.set_permutation(permutation),
+			Err(e)          => {
+				cli_help();
+				eprintln!("{} {}\n", "Error".red().bold(), e);
+				process::exit(1);
+			}
+		}
+	}
+	g.checkpoint_every = Some(CHECKPOINT_EVERY);
+	g.progress_every   = std::io::stdout().is_terminal().then_some(PROGRESS_EVERY);
+	g.deadline         = arg_deadline;
+	g.max_hashes       = arg_max_hashes;
+	g.min_leading_zero = arg_stop_lead;
+	(g, checkpoint, arg_plan)
+
+}
+
 
-	Globals {
-		signature  : arg_signature.clone(),
-		part_name  : part_n.to_owned(),
-		part_args  : part_a.to_owned(),
-		difficulty : arg_difficulty,
-		nn_threads : arg_threads,
-		digit_max  : digit,
-		leading0   : arg_leading0,
-		results    : vec![],
-		max_results: arg_max_results as usize,
-		output     : arg_output,
+/// The `bench` subcommand measures the hashes per second of every backend, from 1 to `t` threads
+/// (all the CPUs by default), and displays the best number of threads. With `o json`, the measures are
+/// also written to a JSON file.
+///
+/// Arguments:
+///
+/// * `args`: The command line arguments after `bench`.
+fn bench(args: &[String]) {
+	let mut arg_signature  : String = "bench(uint256)".to_string();
+	let mut arg_hashes     : u64    = BENCH_HASHES;
+	let mut arg_threads    : usize  = num_cpus::get();
+	let mut arg_json       : bool   = false;
+
+	if (args.len() & 1) != 0 {
+		cli_help();
+		eprintln!("{} wrong number of `bench` parameters\n", "Error".red().bold());
+		process::exit(1);
+	}
+	for pair in args.chunks_exact(2) {
+		match (pair[0].as_str(), pair[1].as_str()) {
+			("s"|"S", arg) => { arg_signature = arg.to_string();},
+			("h"|"H", arg) => { arg_hashes    = arg.parse::<u64>().expect("Invalid `h` parameter ! ").max(1);},
+			("t"|"T", arg) => { arg_threads   = arg.parse::<usize>().expect("Invalid `t` parameter ! ").clamp(1, num_cpus::get());},
+			("o"|"O", arg) => { arg_json      = match arg {"json"|"JSON"=>true, _=>panic!("Invalid `o` parameter, only `json` ! ")};},
+			_              => { cli_help(); panic!("Invalid `bench` parameter `{}` ! ", pair[0]);},
+		}
+	}
+
+	println!();
+	println!("- Signature\t`{}`",            arg_signature);
+	println!("- Hashes\t{} per run",         arg_hashes);
+	println!("- Nbr threads\t1..{} CPU(s)",  arg_threads);
+	println!();
+	println!("{:<10}{:>8}{:>16}{:>12}", "BACKEND", "THREADS", "HASHES/S", "SCALING");
+
+	let backends: Vec<Backend> = Backend::available();
+	let results: Vec<BenchResult> = match bench::run(&arg_signature, arg_hashes, &backends, arg_threads, |res| {
+		println!("{:<10}{:>8}{:>16.0}{:>11.0}%", res.backend.name(), res.threads, res.hashes_per_sec(), res.efficiency * 100.0);
+	}) {
+		Ok(results) => results,
+		Err(e)      => {
+			eprintln!("{} {}\n", "Error".red().bold(), e);
+			process::exit(1);
+		}
+	};
+
+	if let Some(best) = bench::best(&results) {
+		println!();
+		println!("Best : {} backend, {} thread(s), {:.0} hashes/s", best.backend.name().green(), best.threads.to_string().green(), best.hashes_per_sec());
+	}
+	if arg_json {
+		bench::write_json(BENCH_FILE, &results).expect("Failed to write file");
+		println!("Output : {}", BENCH_FILE.cyan());
+	}
+	println!();
+}
+
+
+/// The `show_plan` function measures the hash rate of the search, then displays its expected cost, pass
+/// by pass, instead of running it.
+///
+/// Arguments:
+///
+/// * `g`: The settings of the search.
+fn show_plan(g: &Globals) {
+	let hashes_per_sec: f64 = match planner::measure(g, PLAN_MEASURE) {
+		Ok(hashes_per_sec) => hashes_per_sec,
+		Err(e)             => {
+			eprintln!("{} {}\n", "Error".red().bold(), e);
+			process::exit(1);
+		}
+	};
+	let plan: Plan = planner::plan(g, hashes_per_sec);
+	let time = |duration: Option<Duration>| duration.map_or("-".to_string(), human_duration);
+
+	println!("{:<6}{:>12}{:>16}{:>14}", "PASS", "KEYSPACE", "RESULTS", "ELAPSED");
+	for pass in &plan.passes {
+		println!("{:<6}{:>12}{:>16}{:>14}", pass.digit, human_count(pass.keyspace as f64), human_count(pass.results), time(pass.elapsed));
 	}
+	println!();
+	println!("- Hash rate\t{} hashes/s", human_count(hashes_per_sec));
+	println!("- Expected\t{} hashes, {} for {} result(s){}", human_count(plan.attempts), time(plan.duration), plan.needed,
+		plan.goal_pass.map_or(String::new(), |digit| format!(", in pass #{}", digit)));
+	if let (Some(attempts), Some(stop_lead)) = (plan.stop_attempts, g.min_leading_zero) {
+		println!("- Stop at\t{} hashes, {} for {} leading `0`", human_count(attempts), time(plan.duration_of(attempts)), stop_lead);
+	}
+	if plan.unlikely() {
+		warn_unlikely(&plan);
+	}
+	println!();
+}
+
 
+/// Warns that the requested number of results will likely not be found within the passes.
+fn warn_unlikely(plan: &Plan) {
+	println!("{} {} result(s) unlikely within the passes ({:.0}% chance)\n",
+		"Warning".yellow().bold(), plan.needed, plan.reach_probability * 100.0);
 }
 
 
 fn main() {
-	let g: Globals = init_app();
+	let args: Vec<String> = env::args().skip(1).collect();
+	if args.first().is_some_and(|arg| arg == "bench" || arg == "BENCH") {
+		bench(&args[1..]);
+		process::exit(0);
+	}
+
+	let (g, checkpoint, plan_only) = init_app();
 	//println!("{:?}", g);
 
-	let g = threads_launcher( &g);
-	write_file(g, "All done !");
+	// The plan reads the passes of the settings, they must be valid first
+	if let Err(e) = g.validate() {
+		eprintln!("{} {}\n", "Error".red().bold(), e);
+		process::exit(1);
+	}
+	if plan_only {
+		show_plan(&g);
+		process::exit(0);
+	}
+	// The number of results reachable doesn't depend on the hash rate
+	let plan: Plan = planner::plan(&g, 0.0);
+	if plan.unlikely() {
+		warn_unlikely(&plan);
+	}
+
+	let searcher: Result<Searcher, _> = match checkpoint {
+		Some(cp) => Searcher::resume(g, cp),
+		None     => Searcher::new(g),
+	};
+	let searcher: Searcher = match searcher {
+		Ok(searcher) => searcher,
+		Err(e)       => {
+			eprintln!("{} {}\n", "Error".red().bold(), e);
+			process::exit(1);
+		}
+	};
+
+	// Ctrl-C (SIGINT) or SIGTERM stop the threads, the results found so far are still written
+	let stop: Arc<AtomicBool> = searcher.stop_handle();
+	ctrlc::set_handler(move || {
+		stop.store(true, Ordering::Relaxed);
+	}).expect("Error setting Ctrl-C handler");
+
+	let checkpoint_name: String = output::checkpoint_name(searcher.globals());
+	let screen: Mutex<Screen>   = Mutex::new(Screen::new());
+	let report: SearchReport    = searcher.run_with(|event| match event {
+		Event::PassStart(digit) => screen.lock().expect("Mutex panic ! ").pass_start(digit),
+		Event::PassEnd(_)       => {},
+		Event::Found(_, s)      => screen.lock().expect("Mutex panic ! ").found(in_progress(s.leading_zero)),
+		Event::Progress(p)      => screen.lock().expect("Mutex panic ! ").progress(p),
+		Event::Checkpoint(cp)   => {
+			if let Err(e) = cp.save(&checkpoint_name) {
+				eprintln!("{} checkpoint `{}` : {}", "Error".red().bold(), checkpoint_name, e);
+			}
+		},
+	});
+	screen.into_inner().expect("Mutex panic ! ").end_pass();
+	println!("\n");
+
+	let g: &Globals = searcher.globals();
+	write_file(g, &report);
+
+	if report.stop.is_resumable() {
+		println!("Checkpoint : {} (continue with `select0r resume <checkpoint>`)\n", checkpoint_name.cyan());
+	} else {
+		let _ = std::fs::remove_file(&checkpoint_name);
+	}
 	process::exit(0);
 }
 
@@ -0,0 +1,189 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+
+use crate::globals::Globals;
+use crate::permutation::Permutation;
+use crate::search::StopReason;
+use crate::selector::SignatureResult;
+
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Output {
+	TSV,
+	CSV,
+	JSON,
+	XML,
+	RON,
+}
+
+
+/// Builds the output file name, with the initial signature and some parameters in it.
+///
+/// Arguments:
+///
+/// * `g`: A reference to a struct called `Globals` which contains various configuration parameters for
+/// the file writing process.
+///
+/// Returns:
+///
+/// The file name, e.g. `select0r-execute()--zero=2-max=8-lead=true-cpu=15.xml`.
+pub fn file_name(g: &Globals) -> String {
+	format!("{}.{:?}", base_name(g), g.output).to_lowercase()
+}
+
+
+/// Builds the checkpoint file name, the output file name with a `.checkpoint` extension.
+///
+/// Arguments:
+///
+/// * `g`: A reference to a struct called `Globals` which contains various configuration parameters for
+/// the file writing process.
+///
+/// Returns:
+///
+/// The file name, e.g. `select0r-execute()--zero=2-max=8-lead=true-cpu=15.checkpoint`.
+pub fn checkpoint_name(g: &Globals) -> String {
+	format!("{}.checkpoint", base_name(g)).to_lowercase()
+}
+
+
+fn base_name(g: &Globals) -> String {
+	format!("select0r-{}--zero={}-max={}-lead={}-cpu={}",
+		g.signature, g.difficulty, g.max_results, g.leading0, g.nn_threads)
+}
+
+
+/// The function `write_file` writes the results to a file in the specified format.
+///
+/// Arguments:
+///
+/// * `file_name`: The path of the file to create.
+/// * `g`: The settings of the search, its file format output, and its mode for the extra column, see `note`.
+/// * `results`: The results to write, the initial signature first.
+/// * `stop`: Why the search stopped, recorded after the results, or in a column of every row in `tsv` and `csv`.
+///
+/// Returns:
+///
+/// An `io::Result`, an error if the file can't be created or written.
+pub fn write_file(file_name: &str, g: &Globals, results: &[SignatureResult], stop: StopReason) -> std::io::Result<()> {
+	// Formater factory
+	let formatter: Box<dyn Formatter> = match g.output {
+		Output::TSV  => Box::new(TsvFormatter { stop }),
+		Output::CSV  => Box::new(CsvFormatter { stop }),
+		Output::JSON => Box::new(JsonFormatter),
+		Output::XML  => Box::new(XmlFormatter),
+		Output::RON  => Box::new(RonFormatter),
+	};
+
+	let file: File                  = File::create(file_name)?;
+	let mut writer: BufWriter<File> = BufWriter::new(file);
+
+	// Write header
+	let name: Option<&str> = note_name(g);
+	writer.write_all(formatter.header(name).as_bytes())?;
+
+	// Write lines of data
+	for (i, res) in results.iter().enumerate() {
+		let note: Option<String> = note(g, res);
+		let line: String         = formatter.line(res, i == 0, name.zip(note.as_deref()));
+		writer.write_all(line.as_bytes())?;
+	}
+
+	// Write footer
+	writer.write_all(formatter.footer(stop).as_bytes())?;
+	writer.flush()
+}
+
+
+/// The name of the extra column, `order` in permutation mode, `encoding` with flexible types.
+fn note_name(g: &Globals) -> Option<&'static str> {
+	match (&g.permutation, &g.types) {
+		(Some(_), _)    => Some("order"),
+		(None, Some(_)) => Some("encoding"),
+		(None, None)    => None,
+	}
+}
+
+
+/// The extra column of a result, the order of its arguments (see `Permutation::describe`), or how the
+/// encoding of its types differs from the initial ones (see `FlexibleTypes::encoding`).
+fn note(g: &Globals, res: &SignatureResult) -> Option<String> {
+	if let Some(permutation) = &g.permutation {
+		return Some(Permutation::describe(&permutation.order(res.index.unwrap_or(0))));
+	}
+	let args: &str = &res.signature[res.signature.find('(')?..];
+	g.types.as_ref().map(|types| types.encoding(args))
+}
+
+trait Formatter {
+	fn header(&self, note: Option<&str>) -> String;
+	fn line(&self, res: &SignatureResult, is_first: bool, note: Option<(&str, &str)>) -> String;
+	fn footer(&self, stop: StopReason) -> String;
+}
+
+// Exemple d'Implementation for JSON
+struct JsonFormatter;
+impl Formatter for JsonFormatter {
+	fn header(&self, _note: Option<&str>) -> String { "{\"select0r\":[\n".to_string() }
+	fn footer(&self, stop: StopReason) -> String { format!("], \"stop\":\"{}\"}}\n", stop.name()) }
+	fn line(&self, res: &SignatureResult, is_first: bool, note: Option<(&str, &str)>) -> String {
+		let comma: &str  = if is_first { " " } else { "," };
+		let note: String = note.map_or(String::new(), |(name, note)| format!(", \"{}\":\"{}\"", name, note));
+		format!("\t{}{{ \"selector\":\"{:>08x}\", \"nbr_of_zero\":{}, \"leading_zero\":{}, \"signature\":\"{}\"{} }}\n",
+			comma, res.selector, res.nbr_of_zero, res.leading_zero, res.signature, note)
+	}
+}
+
+// Implementation for TSV, the stop reason in the last column of every row, a row after would read as a result
+struct TsvFormatter {
+	stop: StopReason,
+}
+impl Formatter for TsvFormatter {
+	fn header(&self, note: Option<&str>) -> String { format!("SELECTOR\tNBR_OF_ZERO\tLEADING_ZERO\tSIGNATURE{}\tSTOP\n", note.map_or(String::new(), |name| format!("\t{}", name.to_uppercase()))) }
+	fn footer(&self, _stop: StopReason) -> String { String::new() }
+	fn line(&self, res: &SignatureResult, _is_first: bool, note: Option<(&str, &str)>) -> String {
+		format!("{:>08x}\t{}\t{}\t{}{}\t{}\n",
+			res.selector, res.nbr_of_zero, res.leading_zero, res.signature, note.map_or(String::new(), |(_, note)| format!("\t{}", note)), self.stop.name())
+	}
+}
+
+// Implementation for CSV, the stop reason in the last column of every row
+struct CsvFormatter {
+	stop: StopReason,
+}
+impl Formatter for CsvFormatter {
+	fn header(&self, note: Option<&str>) -> String { format!("SELECTOR,NBR_OF_ZERO,LEADING_ZERO,SIGNATURE{},STOP\n", note.map_or(String::new(), |name| format!(",{}", name.to_uppercase()))) }
+	fn footer(&self, _stop: StopReason) -> String { String::new() }
+	fn line(&self, res: &SignatureResult, _is_first: bool, note: Option<(&str, &str)>) -> String {
+		format!("{:>08x},{},{},{}{},{}\n",
+			res.selector, res.nbr_of_zero, res.leading_zero, res.signature, note.map_or(String::new(), |(_, note)| format!(",\"{}\"", note)), self.stop.name())
+	}
+}
+
+// Implementation for XML
+struct XmlFormatter;
+impl Formatter for XmlFormatter {
+	fn header(&self, _note: Option<&str>) -> String { "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<select0r>\n".to_string() }
+	fn footer(&self, stop: StopReason) -> String { format!("\t<stop>{}</stop>\n</select0r>\n", stop.name()) }
+	fn line(&self, res: &SignatureResult, _is_first: bool, note: Option<(&str, &str)>) -> String {
+		format!("\t<result>\n\t\t<selector>{:>08x}</selector>\n\t\t<nbr_of_zero>{}</nbr_of_zero>\n\t\t<leading_zero>{}</leading_zero>\n\t\t<signature>{}</signature>\n{}\t</result>\n",
+			res.selector, res.nbr_of_zero, res.leading_zero, res.signature, note.map_or(String::new(), |(name, note)| format!("\t\t<{}>{}</{}>\n", name, note, name)))
+	}
+}
+
+// Implementation for RON
+struct RonFormatter;
+impl Formatter for RonFormatter {
+	fn header(&self, _note: Option<&str>) -> String { "Select0r( results: [\n".to_string() }
+	fn footer(&self, stop: StopReason) -> String { format!("], stop: \"{}\",)\n", stop.name()) }
+	fn line(&self, res: &SignatureResult, is_first: bool, note: Option<(&str, &str)>) -> String {
+		let comma: &str  = if is_first { " " } else { "," };
+		let note: String = note.map_or(String::new(), |(name, note)| format!(", {}: \"{}\"", name, note));
+		format!("\t{}(selector: \"{:>08x}\", nbr_of_zero: {}, leading_zero: {}, signature: \"{}\"{})\n",
+			comma, res.selector, res.nbr_of_zero, res.leading_zero, res.signature, note)
+	}
+}
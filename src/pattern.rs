@@ -0,0 +1,386 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::globals::{ConfigError, IteratedValue};
+use crate::syllable;
+
+
+/// The most passes a pattern can expand to, one by combination of its repetition counts and word lengths.
+pub const MAX_PASSES: usize = 1024;
+
+/// The most repetitions of a part of a pattern.
+pub const MAX_REPEAT: u32 = 64;
+
+
+/// The `Class` struct is the set of tokens a position of the generated characters goes through, single
+/// characters or the words of a list with the same length.
+///
+/// Properties:
+///
+/// * `width`: The length of every token.
+/// * `tokens`: The tokens one after the other, in order.
+/// * `word`: The tokens are words, not characters.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Class {
+	width : usize,
+	tokens: Vec<u8>,
+	word  : bool,
+}
+
+
+impl Class {
+
+	/// A class of single characters.
+	pub fn chars(chars: &[u8]) -> Class {
+		Class { width: 1, tokens: chars.to_vec(), word: false }
+	}
+
+
+	/// A class of words, all of the same length.
+	fn words(words: &[&String]) -> Class {
+		Class {
+			width : words[0].len(),
+			tokens: words.iter().flat_map(|word| word.bytes()).collect(),
+			word  : true,
+		}
+	}
+
+
+	/// The number of tokens, the base of the position.
+	pub fn len(&self) -> usize {
+		self.tokens.len() / self.width
+	}
+
+
+	/// A class is never empty once built by a `Pattern`, but maybe once its digits are dropped.
+	pub fn is_empty(&self) -> bool {
+		self.tokens.is_empty()
+	}
+
+
+	/// The length of every token, the number of characters of the position.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+
+	/// The token of a value, from 0 to `len() - 1`.
+	#[inline]
+	pub fn token(&self, value: usize) -> &[u8] {
+		&self.tokens[value * self.width..(value + 1) * self.width]
+	}
+
+
+	/// Tells if the tokens are words.
+	pub fn is_word(&self) -> bool {
+		self.word
+	}
+
+
+	/// The number of tokens that don't start with a digit, those an identifier can start with.
+	pub fn leading_len(&self) -> usize {
+		self.tokens.chunks(self.width).filter(|token| !token[0].is_ascii_digit()).count()
+	}
+
+
+	/// Drops the tokens starting with a digit.
+	pub fn retain_leading(&mut self) {
+		let width: usize = self.width;
+		self.tokens = self.tokens.chunks(width).filter(|token| !token[0].is_ascii_digit()).flatten().copied().collect();
+	}
+
+}
+
+
+/// The `Atom` struct is a part of a pattern, repeated a number of times.
+///
+/// Properties:
+///
+/// * `options`: The classes the atom can be, one by length of the words of a list, a single one for
+/// characters.
+/// * `min`: The fewest repetitions.
+/// * `max`: The most repetitions.
+struct Atom {
+	options: Vec<Class>,
+	min    : u32,
+	max    : u32,
+}
+
+
+/// The `Pattern` struct describes the generated characters position by position, e.g. `v[0-9][a-z]{2}`
+/// or `[A-Z][a-z0-9]{0,3}`, or with words, e.g. `{word}_{word}` or `{Word}[0-9]{0,2}`.
+///
+/// A pattern is a sequence of identifier characters, of classes `[...]` of characters and ranges, and of
+/// words `{list}` of a list, each one maybe followed by a repetition `{n}` or `{n,m}`. The case of the
+/// name of the list is the case of its words : `{word}` in lowercase, `{Word}` capitalized, `{WORD}` in
+/// uppercase. The syllable tables `consonant`, `vowel` and `syllable` are always there, see
+/// `syllable::with_tables`. Every combination of the repetition counts and of the lengths of the words is
+/// a pass, a fixed sequence of classes enumerated as a mixed-radix number, the passes ordered by length.
+///
+/// Properties:
+///
+/// * `source`: The pattern as written.
+/// * `words`: The word lists, by name.
+/// * `passes`: The classes of each pass, the first one for pass 1.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Pattern {
+	source: String,
+	words : Vec<(String, Vec<String>)>,
+	passes: Vec<Vec<Class>>,
+}
+
+
+impl PartialEq for Pattern {
+	fn eq(&self, other: &Pattern) -> bool {
+		self.source == other.source && self.words == other.words
+	}
+}
+
+
+fn is_identifier(c: u8) -> bool {
+	c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+
+/// Parses the class after a `[`, up to the `]`, ranges expanded.
+fn parse_class(bytes: &[u8], at: &mut usize) -> Option<Vec<u8>> {
+	let mut chars: Vec<u8> = vec![];
+	loop {
+		let first: u8 = *bytes.get(*at)?;
+		*at += 1;
+		if first == b']' {
+			break;
+		}
+		let last: u8 = if bytes.get(*at) == Some(&b'-') && bytes.get(*at + 1).is_some_and(|&c| c != b']') {
+			*at += 2;
+			bytes[*at - 1]
+		} else {
+			first
+		};
+		if !is_identifier(first) || !is_identifier(last) || first > last {
+			return None;
+		}
+		chars.extend((first..=last).filter(|&c| is_identifier(c)));
+	}
+
+	let mut unique: Vec<u8> = vec![];
+	for c in chars {
+		if !unique.contains(&c) {
+			unique.push(c);
+		}
+	}
+	(!unique.is_empty()).then_some(unique)
+}
+
+
+/// Parses the repetition after a `{`, up to the `}`.
+fn parse_repeat(bytes: &[u8], at: &mut usize) -> Option<(u32, u32)> {
+	let end: usize   = *at + bytes[*at..].iter().position(|&c| c == b'}')?;
+	let inside: &str = std::str::from_utf8(&bytes[*at..end]).ok()?;
+	*at              = end + 1;
+
+	let (min, max): (&str, &str) = inside.split_once(',').unwrap_or((inside, inside));
+	let (min, max): (u32, u32)   = (min.parse().ok()?, max.parse().ok()?);
+	(min <= max).then_some((min, max))
+}
+
+
+/// Parses the name of a word list after a `{`, up to the `}`, and cases its words like the name.
+///
+/// Returns:
+///
+/// The classes of the words, one by length, shortest first, or `None` if there is no such list.
+fn parse_words(bytes: &[u8], at: &mut usize, words: &[(String, Vec<String>)]) -> Option<Vec<Class>> {
+	let end: usize = *at + bytes[*at..].iter().position(|&c| c == b'}')?;
+	let name: &str = std::str::from_utf8(&bytes[*at..end]).ok()?;
+	*at            = end + 1;
+
+	let list: &Vec<String> = &words.iter().find(|(list, _)| list.eq_ignore_ascii_case(name))?.1;
+	let cased = |word: &String| -> String {
+		if name.bytes().all(|c| !c.is_ascii_lowercase()) {
+			word.to_ascii_uppercase()
+		} else if name.as_bytes()[0].is_ascii_uppercase() {
+			word[..1].to_ascii_uppercase() + &word[1..].to_ascii_lowercase()
+		} else {
+			word.to_ascii_lowercase()
+		}
+	};
+	let mut cased_list: Vec<String> = vec![];
+	for word in list.iter().map(cased) {
+		if !cased_list.contains(&word) {
+			cased_list.push(word);
+		}
+	}
+
+	let mut lengths: Vec<usize> = cased_list.iter().map(String::len).collect();
+	lengths.sort_unstable();
+	lengths.dedup();
+	Some(lengths.into_iter()
+		.map(|len| Class::words(&cased_list.iter().filter(|word| word.len() == len).collect::<Vec<&String>>()))
+		.collect())
+}
+
+
+impl Pattern {
+
+	/// Parses and expands a pattern of characters only.
+	///
+	/// Arguments:
+	///
+	/// * `source`: The pattern, see `Pattern`.
+	///
+	/// Returns:
+	///
+	/// The `Pattern`, or a `ConfigError`, see `Pattern::with_words`.
+	pub fn new(source: &str) -> Result<Pattern, ConfigError> {
+		Pattern::with_words(source, vec![])
+	}
+
+
+	/// Parses and expands a pattern.
+	///
+	/// Arguments:
+	///
+	/// * `source`: The pattern, see `Pattern`.
+	/// * `words`: The word lists the pattern may use, by name, each one with at least one word of
+	/// identifier characters, besides the syllable tables.
+	///
+	/// Returns:
+	///
+	/// The `Pattern`, or a `ConfigError` if a word is invalid, or if the pattern can't be parsed, uses an
+	/// unknown list, repeats a part more than `MAX_REPEAT` times, generates no character, has more than
+	/// `MAX_PASSES` passes, or a keyspace that doesn't fit in an `IteratedValue`.
+	pub fn with_words(source: &str, words: Vec<(String, Vec<String>)>) -> Result<Pattern, ConfigError> {
+		let invalid = || ConfigError::InvalidPattern(source.to_string());
+		for (name, list) in &words {
+			if list.is_empty() {
+				return Err(ConfigError::InvalidWord(name.clone()));
+			}
+			if let Some(word) = list.iter().find(|word| word.is_empty() || !word.bytes().all(is_identifier)) {
+				return Err(ConfigError::InvalidWord(word.clone()));
+			}
+		}
+		let tables: Vec<(String, Vec<String>)> = syllable::with_tables(&words);
+		let bytes: &[u8]                       = source.as_bytes();
+
+		let mut atoms: Vec<Atom> = vec![];
+		let mut at: usize        = 0;
+		while at < bytes.len() {
+			let c: u8 = bytes[at];
+			at += 1;
+			let options: Vec<Class> = match c {
+				b'['                  => vec![Class::chars(&parse_class(bytes, &mut at).ok_or_else(invalid)?)],
+				b'{'                  => parse_words(bytes, &mut at, &tables).ok_or_else(invalid)?,
+				c if is_identifier(c) => vec![Class::chars(&[c])],
+				_                     => return Err(invalid()),
+			};
+			let (min, max): (u32, u32) = if bytes.get(at) == Some(&b'{') && bytes.get(at + 1).is_some_and(u8::is_ascii_digit) {
+				at += 1;
+				parse_repeat(bytes, &mut at).filter(|&(_, max)| max <= MAX_REPEAT).ok_or_else(invalid)?
+			} else {
+				(1, 1)
+			};
+			atoms.push(Atom { options, min, max });
+		}
+
+		// Every combination of the counts and of the options, the first atom the slowest
+		let mut passes: Vec<Vec<Class>> = vec![vec![]];
+		for atom in &atoms {
+			let mut next: Vec<Vec<Class>> = vec![];
+			for pass in &passes {
+				// The passes with `n` repetitions, from those with `n - 1`
+				let mut expanded: Vec<Vec<Class>> = vec![pass.clone()];
+				for n in 0..=atom.max {
+					if n > 0 {
+						expanded = expanded.iter()
+							.flat_map(|prefix| atom.options.iter().map(move |option| [prefix.as_slice(), std::slice::from_ref(option)].concat()))
+							.collect();
+					}
+					if expanded.len() + next.len() > MAX_PASSES + 1 {
+						return Err(invalid());
+					}
+					if n >= atom.min {
+						next.extend(expanded.iter().cloned());
+					}
+				}
+			}
+			passes = next;
+		}
+		passes.retain(|classes| !classes.is_empty());
+		passes.sort_by_key(|classes| classes.iter().map(Class::width).sum::<usize>());
+		if passes.is_empty() || passes.len() > MAX_PASSES {
+			return Err(invalid());
+		}
+
+		let keyspace: Option<IteratedValue> = passes.iter().try_fold(0 as IteratedValue, |total, classes| {
+			classes.iter()
+				.try_fold(1 as IteratedValue, |len, class| len.checked_mul(class.len() as IteratedValue))
+				.and_then(|len| total.checked_add(len))
+		});
+		if keyspace.is_none() {
+			return Err(invalid());
+		}
+
+		Ok(Pattern { source: source.to_string(), words, passes })
+	}
+
+
+	/// The pattern as written.
+	pub fn source(&self) -> &str {
+		&self.source
+	}
+
+
+	/// The word lists, by name, the syllable tables apart unless replaced.
+	pub fn words(&self) -> &[(String, Vec<String>)] {
+		&self.words
+	}
+
+
+	/// The number of passes, one by combination of the repetition counts and of the word lengths.
+	pub fn passes(&self) -> u32 {
+		self.passes.len() as u32
+	}
+
+
+	/// The classes of each position of a pass, from 1 to `passes()`.
+	pub fn classes(&self, pass: u32) -> &[Class] {
+		&self.passes[pass as usize - 1]
+	}
+
+
+	/// Tells if some pass has words or syllables.
+	pub fn has_words(&self) -> bool {
+		self.passes.iter().flatten().any(Class::is_word)
+	}
+
+
+	/// The number of generated characters of a pass that aren't part of a word nor fixed, the fewer the
+	/// more readable its names.
+	pub fn noise(&self, pass: u32) -> usize {
+		self.classes(pass).iter()
+			.filter(|class| !class.is_word() && class.len() > 1)
+			.map(Class::width)
+			.sum()
+	}
+
+}
+
+
+impl fmt::Display for Pattern {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.source)
+	}
+}
+
+
+impl FromStr for Pattern {
+	type Err = ConfigError;
+
+	fn from_str(s: &str) -> Result<Pattern, ConfigError> {
+		Pattern::new(s)
+	}
+}
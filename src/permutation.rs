@@ -0,0 +1,187 @@
+use crate::globals::{ConfigError, IteratedValue};
+
+
+/// The most arguments trading places, their number of orders must fit in an `IteratedValue`.
+pub const MAX_ARGUMENTS: usize = 20;
+
+
+/// Splits the arguments of a signature at their top-level commas, tuples kept whole.
+///
+/// Arguments:
+///
+/// * `args`: The arguments part of a signature, parenthesis included, e.g. `(uint256,(address,bool)[])`.
+///
+/// Returns:
+///
+/// The types of the arguments, e.g. `uint256` and `(address,bool)[]`, none for `()`.
+pub fn split_arguments(args: &str) -> Vec<&str> {
+	let inner: &str = args.strip_prefix('(').and_then(|args| args.strip_suffix(')')).unwrap_or(args);
+	if inner.is_empty() {
+		return vec![];
+	}
+
+	let mut arguments: Vec<&str> = vec![];
+	let mut depth: usize         = 0;
+	let mut start: usize         = 0;
+	for (i, c) in inner.char_indices() {
+		match c {
+			'('              => depth += 1,
+			')'              => depth = depth.saturating_sub(1),
+			',' if depth == 0 => {
+				arguments.push(&inner[start..i]);
+				start = i + 1;
+			},
+			_                => {},
+		}
+	}
+	arguments.push(&inner[start..]);
+	arguments
+}
+
+
+/// The `Permutation` struct enumerates the orders of the arguments of a signature, the name untouched.
+///
+/// The orders are numbered like the suffixes of a pass, `0` being the initial order, each value read as
+/// a Lehmer code of the positions that move.
+///
+/// Properties:
+///
+/// * `arguments`: The types of the arguments, in the initial order.
+/// * `positions`: The positions of the arguments that trade places, sorted, the others never move.
+/// * `factorials`: The weight of each digit of the Lehmer code, most significant first.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Permutation {
+	arguments : Vec<String>,
+	positions : Vec<usize>,
+	factorials: Vec<IteratedValue>,
+}
+
+
+impl Permutation {
+
+	/// Builds the orders of some arguments of a signature.
+	///
+	/// Arguments:
+	///
+	/// * `args`: The arguments part of the signature, parenthesis included.
+	/// * `positions`: The positions of the arguments that trade places, from 0, all of them if empty.
+	///
+	/// Returns:
+	///
+	/// A `Permutation`, or a `ConfigError` if less than 2 or more than `MAX_ARGUMENTS` different positions
+	/// are given, or one past the last argument.
+	pub fn new(args: &str, positions: &[usize]) -> Result<Permutation, ConfigError> {
+		let arguments: Vec<String> = split_arguments(args).into_iter().map(str::to_string).collect();
+		let mut positions: Vec<usize> = if positions.is_empty() {
+			(0..arguments.len()).collect()
+		} else {
+			positions.to_vec()
+		};
+		positions.sort_unstable();
+		positions.dedup();
+
+		if !(2..=MAX_ARGUMENTS).contains(&positions.len()) || positions.iter().any(|&at| at >= arguments.len()) {
+			return Err(ConfigError::InvalidPermutation(args.to_string()));
+		}
+
+		let factorials: Vec<IteratedValue> = (0..positions.len() as IteratedValue)
+			.rev()
+			.map(|n| (1..=n).product())
+			.collect();
+		Ok(Permutation {
+			arguments,
+			positions,
+			factorials,
+		})
+	}
+
+
+	/// The types of the arguments, in the initial order.
+	pub fn arguments(&self) -> &[String] {
+		&self.arguments
+	}
+
+
+	/// The positions of the arguments that trade places, from 0.
+	pub fn positions(&self) -> &[usize] {
+		&self.positions
+	}
+
+
+	/// The number of orders, the initial one included.
+	pub fn len(&self) -> IteratedValue {
+		self.factorials.first().map_or(1, |factorial| factorial * self.positions.len() as IteratedValue)
+	}
+
+
+	/// Always `false`, there is at least the initial order.
+	pub fn is_empty(&self) -> bool {
+		false
+	}
+
+
+	/// The length of the arguments between the parenthesis, the same in every order.
+	pub fn width(&self) -> usize {
+		self.arguments.iter().map(String::len).sum::<usize>() + self.arguments.len().saturating_sub(1)
+	}
+
+
+	/// The initial position of each argument in the order of `value`.
+	pub fn order(&self, value: IteratedValue) -> Vec<usize> {
+		let mut order: Vec<usize> = vec![0; self.arguments.len()];
+		self.order_into(value, &mut order);
+		order
+	}
+
+
+	/// Same as `order`, in a buffer of the size of the arguments, without allocation. The values past the
+	/// last order wrap around, like the suffixes of a pass.
+	pub fn order_into(&self, value: IteratedValue, order: &mut [usize]) {
+		let k: usize                         = self.positions.len();
+		let mut left: [usize; MAX_ARGUMENTS] = [0; MAX_ARGUMENTS];
+		let mut value: IteratedValue         = value % self.len();
+		left[..k].copy_from_slice(&self.positions);
+		for (i, at) in order.iter_mut().enumerate() {
+			*at = i;
+		}
+		for (j, &factorial) in self.factorials.iter().enumerate() {
+			let pick: usize = (value / factorial) as usize;
+			value          %= factorial;
+			order[self.positions[j]] = left[pick];
+			left.copy_within(pick + 1..k - j, pick);
+		}
+	}
+
+
+	/// Writes the arguments in `order`, without the parenthesis, to a buffer of `width` bytes.
+	pub fn write(&self, order: &[usize], buffer: &mut [u8]) {
+		let mut offset: usize = 0;
+		for (i, &from) in order.iter().enumerate() {
+			if i > 0 {
+				buffer[offset] = b',';
+				offset        += 1;
+			}
+			let argument: &[u8] = self.arguments[from].as_bytes();
+			buffer[offset..offset + argument.len()].copy_from_slice(argument);
+			offset += argument.len();
+		}
+	}
+
+
+	/// Checks that the arguments of the same type keep their initial order, so that each signature is
+	/// only reported once, by the first order giving it.
+	pub fn is_canonical(&self, order: &[usize]) -> bool {
+		order.iter().enumerate().all(|(i, &a)| {
+			order[i + 1..].iter().all(|&b| self.arguments[a] != self.arguments[b] || a < b)
+		})
+	}
+
+
+	/// Describes an order by the initial positions of its arguments, from 1, e.g. `3,1,2`.
+	pub fn describe(order: &[usize]) -> String {
+		order.iter().map(|at| (at + 1).to_string()).collect::<Vec<String>>().join(",")
+	}
+
+}
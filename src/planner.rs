@@ -0,0 +1,251 @@
+use std::time::{Duration, Instant};
+
+use crate::globals::{ConfigError, Globals, IteratedValue};
+use crate::search::{SearchReport, Searcher};
+use crate::selector::{binomial, leading_zeros_probability, signature_result, zeros_probability};
+
+
+/// Euler–Mascheroni constant, the expected number of records among `n` draws is about `ln(n) + γ`.
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+/// Below this number of hits the expected number of records is summed, not approximated.
+const EXACT_RECORDS: f64 = 1e4;
+
+/// Below this probability, the planner warns that the goal is unlikely to be reached.
+pub const UNLIKELY: f64 = 0.5;
+
+
+/// The `PassPlan` struct is the expectation of a search at the end of one of its passes.
+///
+/// Properties:
+///
+/// * `digit`: The pass, see `Globals::classes`.
+/// * `keyspace`: The number of suffixes of the pass.
+/// * `attempts`: The number of hashes from the start of the search to the end of the pass.
+/// * `results`: The expected number of results found from the start of the search to the end of the pass,
+/// the initial signature excluded.
+/// * `elapsed`: The expected duration from the start of the search to the end of the pass.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct PassPlan {
+	pub digit   : u32,
+	pub keyspace: IteratedValue,
+	pub attempts: f64,
+	pub results : f64,
+	pub elapsed : Option<Duration>,
+}
+
+
+/// The `Plan` struct is the expected cost of a search, from the binomial probability of the zero bytes of
+/// a selector and the keyspace of each pass.
+///
+/// Properties:
+///
+/// * `hashes_per_sec`: The hash rate the durations are computed with.
+/// * `probability`: The probability that a candidate has at least `Globals::difficulty` zero bytes.
+/// * `needed`: The number of results to find, the initial signature excluded.
+/// * `attempts`: The expected number of hashes to find them.
+/// * `duration`: The expected duration to find them, `None` if too long to be told.
+/// * `goal_pass`: The pass in which the last of them is expected, `None` past the last pass.
+/// * `reach_probability`: The probability to find them within the `Globals::digit_max` passes.
+/// * `stop_attempts`: The expected number of hashes to the first result with `Globals::min_leading_zero`
+/// leading zero bytes, if any.
+/// * `passes`: The expectation at the end of each pass.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Plan {
+	pub hashes_per_sec   : f64,
+	pub probability      : f64,
+	pub needed           : usize,
+	pub attempts         : f64,
+	pub duration         : Option<Duration>,
+	pub goal_pass        : Option<u32>,
+	pub reach_probability: f64,
+	pub stop_attempts    : Option<f64>,
+	pub passes           : Vec<PassPlan>,
+}
+
+
+impl Plan {
+
+	/// The requested number of results will likely not be found within the passes.
+	pub fn unlikely(&self) -> bool {
+		self.reach_probability < UNLIKELY
+	}
+
+
+	/// The expected duration of a number of hashes at the hash rate of the plan, `None` if too long to be told.
+	pub fn duration_of(&self, attempts: f64) -> Option<Duration> {
+		duration_of(attempts, self.hashes_per_sec)
+	}
+
+}
+
+
+fn duration_of(attempts: f64, hashes_per_sec: f64) -> Option<Duration> {
+	if hashes_per_sec <= 0.0 {
+		return None;
+	}
+	Duration::try_from_secs_f64(attempts / hashes_per_sec).ok()
+}
+
+
+/// The expected number of results among `hits` candidates with enough zero bytes.
+///
+/// In leading zeros mode, a hit is kept only if its selector is lower than all the previous ones, and
+/// lower than the initial one, a fraction `below` of the selectors. The i-th hit is such a record with
+/// the probability `(1 - (1 - below)^i) / i`.
+fn expected_results(g: &Globals, hits: f64, below: f64) -> f64 {
+	if !g.leading0 {
+		return hits;
+	}
+	if hits < EXACT_RECORDS {
+		let mut above: f64 = 1.0;
+		return (1..=hits as u64)
+			.fold(0.0, |sum, i| {
+				above *= 1.0 - below;
+				sum + (1.0 - above) / i as f64
+			});
+	}
+	((hits * below).ln() + EULER_GAMMA).max(0.0)
+}
+
+
+/// The number of hits needed to expect `needed` results, the inverse of `expected_results`.
+fn expected_hits(g: &Globals, needed: usize, below: f64) -> f64 {
+	if !g.leading0 || needed == 0 {
+		return needed as f64;
+	}
+
+	// The records grow like the logarithm of the hits, bisected on it
+	let (mut low, mut high): (f64, f64) = (0.0, 64.0 * std::f64::consts::LN_2 + (needed as f64));
+	for _ in 0..100 {
+		let middle: f64 = (low + high) / 2.0;
+		if expected_results(g, middle.exp(), below) < needed as f64 {
+			low = middle;
+		} else {
+			high = middle;
+		}
+	}
+	high.exp()
+}
+
+
+/// The probability that a Poisson variable of mean `lambda` is at least `n`.
+fn poisson_at_least(lambda: f64, n: usize) -> f64 {
+	if n == 0 {
+		return 1.0;
+	}
+	if lambda <= 0.0 {
+		return 0.0;
+	}
+
+	// 1 - P(N < n), the terms computed in log space so a large mean doesn't underflow them
+	let mut ln_factorial: f64 = 0.0;
+	let mut below: f64        = 0.0;
+	for k in 0..n {
+		if k > 0 {
+			ln_factorial += (k as f64).ln();
+		}
+		below += (-lambda + k as f64 * lambda.ln() - ln_factorial).exp();
+	}
+	(1.0 - below).clamp(0.0, 1.0)
+}
+
+
+/// The probability that a selector has at least `nn_leading` leading zero bytes and at least `nn_zeros`
+/// zero bytes.
+fn stop_probability(nn_leading: u32, nn_zeros: u32) -> f64 {
+	let leading: u32 = nn_leading.min(4);
+	let p: f64       = 1.0 / 256.0;
+	let rest: u32    = 4 - leading;
+	let more: u32    = nn_zeros.saturating_sub(leading);
+	if more > rest {
+		return 0.0;
+	}
+
+	// The bytes after the leading ones are free, among them `more` zeros at least
+	leading_zeros_probability(leading) * (more..=rest)
+		.map(|k| binomial(rest, k) * p.powi(k as i32) * (1.0 - p).powi((rest - k) as i32))
+		.sum::<f64>()
+}
+
+
+/// Plans a search : the expected number of hashes and duration to find `Globals::max_results` results,
+/// pass by pass.
+///
+/// Arguments:
+///
+/// * `g`: The settings of the search.
+/// * `hashes_per_sec`: The hash rate of the search, see `measure`.
+///
+/// Returns:
+///
+/// The `Plan` of the search.
+pub fn plan(g: &Globals, hashes_per_sec: f64) -> Plan {
+	let probability: f64 = zeros_probability(g.difficulty);
+	let needed: usize    = g.max_results.saturating_sub(1);
+	let below: f64       = (signature_result(&g.signature).selector as f64 / 2f64.powi(32)).max(2f64.powi(-32));
+
+	let mut attempts: f64          = 0.0;
+	let mut goal_pass: Option<u32> = None;
+	let mut passes: Vec<PassPlan>  = vec![];
+	for digit in 1..=g.digit_max {
+		let keyspace: IteratedValue = g.pass_len(digit);
+		attempts += keyspace as f64;
+		let results: f64 = expected_results(g, attempts * probability, below);
+		if goal_pass.is_none() && results >= needed as f64 {
+			goal_pass = Some(digit);
+		}
+		passes.push(PassPlan {
+			digit,
+			keyspace,
+			attempts,
+			results,
+			elapsed : duration_of(attempts, hashes_per_sec),
+		});
+	}
+
+	let attempts: f64 = expected_hits(g, needed, below) / probability;
+	let total: f64    = passes.last().map_or(0.0, |pass| pass.results);
+	Plan {
+		hashes_per_sec,
+		probability,
+		needed,
+		attempts,
+		duration         : duration_of(attempts, hashes_per_sec),
+		goal_pass,
+		reach_probability: poisson_at_least(total, needed),
+		stop_attempts    : g.min_leading_zero.map(|k| 1.0 / stop_probability(k, g.difficulty)),
+		passes,
+	}
+}
+
+
+/// Measures the hash rate of a search with the settings of `g`, its backend and number of threads, by
+/// running it for a while without keeping any result.
+///
+/// Arguments:
+///
+/// * `g`: The settings of the search.
+/// * `duration`: How long to measure.
+///
+/// Returns:
+///
+/// The number of hashes per second, or a `ConfigError` if the settings are invalid.
+pub fn measure(g: &Globals, duration: Duration) -> Result<f64, ConfigError> {
+	let mut g: Globals = g.clone();
+	g.difficulty       = 3;
+	g.leading0         = false;
+	g.max_results      = usize::MAX;
+	g.checkpoint_every = None;
+	g.progress_every   = None;
+	g.deadline         = Some(duration);
+	g.max_hashes       = None;
+	g.min_leading_zero = None;
+
+	let searcher: Searcher   = Searcher::new(g)?;
+	let start: Instant       = Instant::now();
+	let report: SearchReport = searcher.run_with(|_| {});
+	Ok(report.hashes as f64 / start.elapsed().as_secs_f64().max(f64::EPSILON))
+}
@@ -0,0 +1,932 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use crossbeam::thread;
+
+use crate::checkpoint::Checkpoint;
+use crate::globals::{AtomicIteratedValue, ConfigError, Globals, IteratedValue};
+use crate::keccak::{Template, MAX_LANES};
+use crate::pattern::{Class, Pattern};
+use crate::permutation::Permutation;
+use crate::selector::{count_leading_zeros, count_zeros, signature_result, zeros_probability, SignatureResult};
+
+
+/// The `Event` enum is what a `Searcher` reports while running, see `Searcher::run_with`.
+#[derive(Debug)]
+pub enum Event<'a> {
+	/// The first suffixes of a pass are given out to a thread, see `Globals::classes`.
+	PassStart(u32),
+	/// All the suffixes of a pass are searched, the threads may already be in the next ones.
+	PassEnd(u32),
+	/// A new result has been kept, with the pass it comes from. In deterministic mode, it may still be
+	/// dropped for one found before it in the keyspace order.
+	Found(u32, &'a SignatureResult),
+	/// A snapshot of the search, every `Globals::checkpoint_every` and when the search is stopped.
+	Checkpoint(&'a Checkpoint),
+	/// The statistics of the search, every `Globals::progress_every`.
+	Progress(&'a Progress),
+}
+
+
+/// Number of hashes a thread reserves at once, the deadline and the hash budget are checked between
+/// two batches.
+const HASH_BATCH: u64 = 1024;
+
+
+/// Number of consecutive suffixes a thread takes from the queue at once.
+const CHUNK: IteratedValue = 1 << 14;
+
+
+/// The `StopReason` enum tells why a search stopped, the first stop condition met wins.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum StopReason {
+	/// A selector with only zeros, or `max_results` results.
+	Goal,
+	/// A result with at least `Globals::min_leading_zero` leading zeros.
+	LeadingZero,
+	/// `Globals::deadline` is over.
+	Deadline,
+	/// `Globals::max_hashes` hashes computed by this run, those of a resumed checkpoint apart.
+	HashBudget,
+	/// Stopped from outside, see `Searcher::stop_handle`.
+	Interrupted,
+	/// All the passes ran out.
+	AllDone,
+}
+
+
+impl StopReason {
+
+	/// Tells if the search stopped before its end, and can be resumed from its last `Checkpoint`.
+	pub fn is_resumable(&self) -> bool {
+		matches!(self, StopReason::Deadline | StopReason::HashBudget | StopReason::Interrupted)
+	}
+
+
+	/// A short name for the stop condition, as written in the output file.
+	pub fn name(&self) -> &'static str {
+		match self {
+			StopReason::Goal        => "goal",
+			StopReason::LeadingZero => "leading_zero",
+			StopReason::Deadline    => "deadline",
+			StopReason::HashBudget  => "hash_budget",
+			StopReason::Interrupted => "interrupted",
+			StopReason::AllDone     => "all_done",
+		}
+	}
+
+}
+
+impl fmt::Display for StopReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			StopReason::Goal        => write!(f, "Goal reached !"),
+			StopReason::LeadingZero => write!(f, "Leading zeros reached !"),
+			StopReason::Deadline    => write!(f, "Deadline reached !"),
+			StopReason::HashBudget  => write!(f, "Hash budget spent !"),
+			StopReason::Interrupted => write!(f, "Interrupted !"),
+			StopReason::AllDone     => write!(f, "All done !"),
+		}
+	}
+}
+
+
+/// The `SearchReport` struct is what `Searcher::run_with` returns.
+///
+/// Properties:
+///
+/// * `results`: The results, the initial signature first, the most readable first in a search with words.
+/// * `stop`: Why the search stopped.
+/// * `hashes`: The number of hashes computed, those of the resumed checkpoint included.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SearchReport {
+	pub results: Vec<SignatureResult>,
+	pub stop   : StopReason,
+	pub hashes : u64,
+}
+
+
+/// The keyspace left to search, given out to the threads by chunks, see `Shared::claim`.
+///
+/// Properties:
+///
+/// * `pending`: The ranges of indexes left over by a resumed `Checkpoint`, given out first, in order.
+/// * `next`: The first index never given out.
+/// * `pass`: The last pass given out, to report each `Event::PassStart` once.
+struct Queue {
+	pending: Vec<(IteratedValue, IteratedValue)>,
+	next   : IteratedValue,
+	pass   : u32,
+}
+
+
+/// The range of the keyspace a thread is searching, `at` is the next index to compute.
+struct Slot {
+	at : AtomicIteratedValue,
+	end: AtomicIteratedValue,
+}
+
+
+/// The `Progress` struct holds the statistics of a running search, see `Event::Progress`.
+///
+/// Properties:
+///
+/// * `hashes`: The number of hashes computed, those of the resumed checkpoint included.
+/// * `elapsed`: The duration of the run.
+/// * `hashes_per_sec`: The hash rate of the run.
+/// * `pass`: The last pass started.
+/// * `pass_done`: The part of the suffixes of `pass` searched, from 0.0 to 1.0.
+/// * `next_hit`: The expected time to the next selector with `Globals::difficulty` zeros at this hash
+/// rate, `None` before the rate is known.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Progress {
+	pub hashes        : u64,
+	pub elapsed       : Duration,
+	pub hashes_per_sec: f64,
+	pub pass          : u32,
+	pub pass_done     : f64,
+	pub next_hit      : Option<Duration>,
+}
+
+
+/// State shared by the threads of a single run.
+///
+/// Each thread keeps the results it finds in its own buffer, merged with the results of a resumed
+/// checkpoint (`base`) at the end. `count` is the number of ranks reserved by the results, `kept` the number
+/// of results pushed, and `best` the best selector so far in leading zeros mode, only replaced by a better
+/// one holding a rank, so that it is always kept.
+///
+/// A range taken from `queue` is moved to the `slots` of the thread while `queue` is locked, and a slot
+/// is only moved forward past a kept result while the buffer of its thread is locked, so a `Checkpoint`
+/// taken under all the locks never misses nor repeats one.
+///
+/// `left` is the number of suffixes of each pass not searched yet, indexed by pass.
+///
+/// `initial` is the selector of the initial signature, a result in leading zeros mode must be better.
+struct Shared {
+	base    : Vec<SignatureResult>,
+	initial : u32,
+	buffers : Vec<Mutex<Vec<SignatureResult>>>,
+	count   : AtomicUsize,
+	kept    : AtomicUsize,
+	best    : AtomicU32,
+	done    : AtomicBool,
+	reason  : OnceLock<StopReason>,
+	deadline: Option<Instant>,
+	budget  : Option<u64>,
+	hashes  : AtomicU64,
+	queue   : Mutex<Queue>,
+	slots   : Vec<Slot>,
+	left    : Vec<AtomicIteratedValue>,
+}
+
+
+/// The `Searcher` looks for function names whose selectors have the most zero bytes.
+///
+/// It is built from a validated `Globals` and does no printing and no file writing, the results are
+/// returned by `run` (or reported on the fly by `run_with`).
+pub struct Searcher {
+	g     : Globals,
+	stop  : Arc<AtomicBool>,
+	resume: Option<Checkpoint>,
+}
+
+
+/// The `Candidate` struct is the signature a thread is about to hash, kept in a reusable buffer.
+///
+/// The function name, the separator and the arguments never move, only the generated bytes are updated
+/// in place, like an odometer, from a value to the next one of the thread. Each position has its own
+/// characters, and its own base. In permutation mode, the arguments are written again in the order of
+/// each value instead.
+///
+/// Properties:
+///
+/// * `buffer`: The whole signature, `Globals::head` + suffix + `Globals::tail` of its pass.
+/// * `digits`: The digits of the current value, most significant first, each one in the base of its position.
+/// * `at`, `end`: The range of the suffix in `buffer`.
+/// * `offsets`: The offset of each position in `buffer`, a position may be a whole word.
+/// * `classes`: The tokens of each position, see `Globals::classes`.
+/// * `permutation`: The orders of the arguments in permutation mode, see `Globals::permutation`.
+/// * `value`: The current value in permutation mode.
+/// * `order`: The initial position of each argument in the current order, in permutation mode.
+struct Candidate<'a> {
+	buffer     : Vec<u8>,
+	digits     : Vec<usize>,
+	at         : usize,
+	end        : usize,
+	offsets    : Vec<usize>,
+	classes    : &'a [Class],
+	permutation: Option<&'a Permutation>,
+	value      : IteratedValue,
+	order      : Vec<usize>,
+}
+
+
+impl<'a> Candidate<'a> {
+
+	/// Builds the candidate of `value` in the pass `digit`, with a suffix of the tokens of `classes`.
+	fn new(g: &'a Globals, digit: u32, classes: &'a [Class], value: IteratedValue) -> Candidate<'a> {
+		let head: Vec<u8>  = g.head(digit).into_bytes();
+		let zeros: Vec<u8> = match &g.permutation {
+			Some(permutation) => vec![b','; permutation.width()],
+			None              => classes.iter().flat_map(|class| class.token(0)).copied().collect(),
+		};
+		let offsets: Vec<usize> = classes.iter()
+			.scan(head.len(), |offset, class| {
+				*offset += class.width();
+				Some(*offset - class.width())
+			})
+			.collect();
+		let mut candidate: Candidate = Candidate {
+			buffer     : [head.as_slice(), &zeros, g.tail(digit).as_bytes()].concat(),
+			digits     : vec![0; classes.len()],
+			at         : head.len(),
+			end        : head.len() + zeros.len(),
+			offsets,
+			classes,
+			permutation: g.permutation.as_ref(),
+			value      : 0,
+			order      : vec![0; g.permutation.as_ref().map_or(0, |permutation| permutation.arguments().len())],
+		};
+		candidate.step(value);
+		candidate
+	}
+
+
+	/// The suffix, the only part of the signature that changes.
+	#[inline]
+	fn suffix(&self) -> &[u8] {
+		&self.buffer[self.at..self.end]
+	}
+
+
+	/// Adds `step` to the current value, only the digits reached by the carry are written.
+	#[inline]
+	fn step(&mut self, step: IteratedValue) {
+		if let Some(permutation) = self.permutation {
+			self.value += step;
+			permutation.order_into(self.value, &mut self.order);
+			permutation.write(&self.order, &mut self.buffer[self.at..self.end]);
+			return;
+		}
+		let mut carry: IteratedValue = step;
+		for (i, digit) in self.digits.iter_mut().enumerate().rev() {
+			if carry == 0 {
+				break;
+			}
+			// Most steps don't carry, no division then
+			let class: &Class       = &self.classes[i];
+			let base: IteratedValue = class.len() as IteratedValue;
+			let sum: IteratedValue  = *digit as IteratedValue + carry;
+			let (value, next)       = if sum < base { (sum, 0) } else { (sum % base, sum / base) };
+			let offset: usize       = self.offsets[i];
+			*digit                  = value as usize;
+			self.buffer[offset..offset + class.width()].copy_from_slice(class.token(value as usize));
+			carry                   = next;
+		}
+	}
+
+
+	/// The signature as a `String`, only built for the candidates that are kept.
+	fn signature(&self) -> String {
+		// Direct conversion (we know that the alphabet is ASCII, and the rest comes from a `String`)
+		unsafe { String::from_utf8_unchecked(self.buffer.clone()) }
+	}
+
+}
+
+
+/// The function takes in some parameters, computes a signature result based on those parameters, and
+/// returns it as an option.
+///
+/// Arguments:
+///
+/// * `g`: A reference to a struct called `Globals` which contains global variables and settings for the
+/// computation.
+/// * `selector_u32`: The selector of the candidate, hashed with the others of its batch.
+/// * `candidate`: The hashed `Candidate`.
+/// * `index`: The index of the candidate in the keyspace.
+///
+/// Returns:
+///
+/// The function `compute` returns an `Option<SignatureResult>`.
+#[inline]
+fn compute(g: &Globals, selector_u32: u32, candidate: &Candidate, index: IteratedValue) -> Option<SignatureResult> {
+	let zero_counter: u32 = count_zeros(selector_u32);
+
+	//if selector_u32 == 0 {return None;}
+	if zero_counter < g.difficulty {return None;}
+	// In case, permutation or flexible types mode, the first candidate is the initial signature
+	if (g.cases || candidate.permutation.is_some() || g.types.is_some()) && index == 0 {return None;}
+	if candidate.permutation.is_some_and(|permutation| !permutation.is_canonical(&candidate.order)) {return None;}
+
+	let leading_zero = count_leading_zeros(selector_u32);
+
+	Some( SignatureResult {
+		signature   : candidate.signature(),
+		selector    : selector_u32,
+		leading_zero,
+		nbr_of_zero : zero_counter,
+		index       : Some(index),
+	})
+
+}
+
+
+/// Ranks the results of a search with words or syllables, the initial signature apart : the most zeros
+/// first, then the fewest generated characters out of the words (see `Pattern::noise`), then the shortest
+/// names. The order of the keyspace is kept otherwise.
+///
+/// In leading zeros mode, each result is better than the previous one, nothing is ranked.
+fn rank(g: &Globals, results: &mut [SignatureResult]) {
+	let pattern: &Pattern = match &g.pattern {
+		Some(pattern) if pattern.has_words() && !g.leading0 => pattern,
+		_ => return,
+	};
+	let noise = |index: IteratedValue| match g.generated_pass(g.locate(index).0) {
+		0    => 0,
+		pass => pattern.noise(pass),
+	};
+	if let Some(found) = results.get_mut(1..) {
+		found.sort_by_key(|s| (
+			std::cmp::Reverse(s.nbr_of_zero),
+			s.index.map_or(0, noise),
+			s.signature.len(),
+		));
+	}
+}
+
+
+/// The function `thread` takes in some parameters and performs computations using a hashing algorithm,
+/// updating shared results and reporting progress along the way.
+///
+/// The thread takes chunks of the keyspace from the queue until it is empty, whatever their pass. The
+/// values of a chunk are hashed `g.backend.lanes()` at a time, the last batch may be partial.
+///
+/// In deterministic mode, a thread keeps every result of its chunks, or in leading zeros mode every
+/// selector better than the previous ones of the chunk, and the goal is only checked between two chunks,
+/// see `Shared::settle`.
+///
+/// Arguments:
+///
+/// * `g`: The parameter `g` is of type `Globals` and represents a struct that contains global variables
+/// and settings for the program.
+/// * `shared`: The result buffers, the `done` flag and the queue shared by all the threads of the run.
+/// * `stop`: Set from outside the run to ask the threads to stop.
+/// * `on_event`: Called for every new result kept, and at the start and the end of the passes.
+/// * `templates`: The `Template` of each pass, the first one for pass 1.
+/// * `slot_idx`: The index of the thread, the slot of its range in `shared.slots` and of its buffer.
+fn thread<F>(g: &Globals, shared: &Shared, stop: &AtomicBool, on_event: &F, templates: &[Template], slot_idx: usize)
+where
+	F: Fn(Event) + Sync,
+{
+	let slot: &Slot                          = &shared.slots[slot_idx];
+	let buffer: &Mutex<Vec<SignatureResult>> = &shared.buffers[slot_idx];
+	let mut reserved: u64                    = 0;
+	let lanes: IteratedValue                 = g.backend.lanes() as IteratedValue;
+	let mut selectors: Vec<u32>              = vec![0; lanes as usize];
+
+	'chunks: while let Some((digit, start, end)) = shared.claim(g, slot_idx, on_event) {
+		let template: &Template            = &templates[digit as usize - 1];
+		let offset: IteratedValue          = g.pass_start(digit);
+		let mut batch: IteratedValue       = start;
+		let classes: Vec<Class>            = g.classes(digit);
+		let mut candidates: Vec<Candidate> = (0..lanes).map(|k| Candidate::new(g, digit, &classes, start + k)).collect();
+		let mut chunk_best: u32            = shared.initial;
+
+		while batch < end {
+			let mut suffixes: [&[u8]; MAX_LANES] = [&[]; MAX_LANES];
+			for (suffix, candidate) in suffixes.iter_mut().zip(&candidates) {
+				*suffix = candidate.suffix();
+			}
+			template.selectors_with(g.backend, &suffixes[..candidates.len()], &mut selectors);
+
+			for (k, candidate) in candidates.iter().enumerate() {
+				let value: IteratedValue = batch + k as IteratedValue;
+				if value >= end {
+					break;
+				}
+				if shared.done.load(Ordering::Relaxed) || stop.load(Ordering::Relaxed) {
+					break 'chunks;
+				}
+				if reserved == 0 {
+					reserved = shared.reserve(g);
+					if reserved == 0 {
+						break 'chunks;
+					}
+				}
+				reserved -= 1;
+				let next: IteratedValue = offset + value + 1;
+
+				let s: SignatureResult = match compute(g, selectors[k], candidate, next - 1) {
+					Some(s) if g.deterministic && (!g.leading0 || s.selector < chunk_best) => s,
+					Some(s) if !g.deterministic && (!g.leading0 || s.selector < shared.best.load(Ordering::Relaxed)) => s,
+					_ => {
+						slot.at.store(next, Ordering::Relaxed);
+						continue;
+					}
+				};
+
+				// Whether it is a result depends on the chunks before it, settled later
+				if g.deterministic {
+					let mut results: MutexGuard<'_, Vec<SignatureResult>> = buffer.lock().expect("Mutex panic ! ");
+					slot.at.store(next, Ordering::Relaxed);
+					chunk_best = s.selector;
+					results.push(s.clone());
+					drop(results);
+					on_event(Event::Found(digit, &s));
+					continue;
+				}
+
+				// A rank in the results, past `max_results` the other ranks are all taken, unless one is given
+				// back below, so it is given back too
+				let rank: usize = shared.count.fetch_add(1, Ordering::Relaxed);
+				if rank >= g.max_results {
+					shared.count.fetch_sub(1, Ordering::Relaxed);
+					slot.at.store(next, Ordering::Relaxed);
+					continue;
+				}
+
+				// In leading zeros mode, it must still beat the best selector, else its rank is given back
+				if g.leading0 && !shared.improve(s.selector) {
+					shared.count.fetch_sub(1, Ordering::Relaxed);
+					slot.at.store(next, Ordering::Relaxed);
+					continue;
+				}
+
+				let mut results: MutexGuard<'_, Vec<SignatureResult>> = buffer.lock().expect("Mutex panic ! ");
+				slot.at.store(next, Ordering::Relaxed);
+				results.push(s.clone());
+				drop(results);
+				on_event(Event::Found(digit, &s));
+
+				let kept: usize = shared.kept.fetch_add(1, Ordering::Relaxed) + 1;
+				if (g.leading0 && s.selector == 0) || kept >= g.max_results {
+					shared.finish(StopReason::Goal);
+					break 'chunks;
+				}
+				if g.min_leading_zero.is_some_and(|k| s.leading_zero >= k) {
+					shared.finish(StopReason::LeadingZero);
+					break 'chunks;
+				}
+			}// candidates.iter()
+
+			batch += lanes;
+			for candidate in candidates.iter_mut() {
+				candidate.step(lanes);
+			}
+		}// while batch < end
+
+		// The whole chunk is searched
+		if shared.left[digit as usize].fetch_sub(end - start, Ordering::Relaxed) == end - start {
+			on_event(Event::PassEnd(digit));
+		}
+		if g.deterministic {
+			shared.settle(g);
+		}
+	}// while let Some(chunk)
+
+	// Give back the hashes reserved but not computed
+	shared.hashes.fetch_sub(reserved, Ordering::Relaxed);
+
+}
+
+
+/// Sets a flag when dropped, so the checkpoint thread also ends when a pass panics.
+struct SetOnDrop<'a>(&'a AtomicBool);
+
+impl Drop for SetOnDrop<'_> {
+	fn drop(&mut self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+}
+
+
+impl Shared {
+
+	/// Records why the search stops, unless another stop condition was met first, and stops the threads.
+	fn finish(&self, reason: StopReason) {
+		let _ = self.reason.set(reason);
+		self.done.store(true, Ordering::Relaxed);
+	}
+
+
+	/// Replaces the best selector with `selector` if it is better, with a compare-and-swap.
+	///
+	/// Returns:
+	///
+	/// `true` if `selector` is the new best selector.
+	fn improve(&self, selector: u32) -> bool {
+		let mut best: u32 = self.best.load(Ordering::Relaxed);
+		while selector < best {
+			match self.best.compare_exchange_weak(best, selector, Ordering::Relaxed, Ordering::Relaxed) {
+				Ok(_)        => return true,
+				Err(current) => best = current,
+			}
+		}
+		false
+	}
+
+
+	/// Locks the result buffers of all the threads.
+	fn lock_buffers(&self) -> Vec<MutexGuard<'_, Vec<SignatureResult>>> {
+		self.buffers.iter().map(|buffer| buffer.lock().expect("Mutex panic ! ")).collect()
+	}
+
+
+	/// Merges the results of the threads after those of the resumed checkpoint.
+	///
+	/// In leading zeros mode, each result is better than the previous one.
+	///
+	/// In deterministic mode, the results are sorted by index, and only the first ones are kept : in
+	/// leading zeros mode those better than all the previous ones, up to the first one with
+	/// `Globals::min_leading_zero` leading zeros, and at most `Globals::max_results`. A result dropped
+	/// there can't come back whatever is found before it later.
+	fn results(&self, g: &Globals, buffers: &[MutexGuard<'_, Vec<SignatureResult>>]) -> Vec<SignatureResult> {
+		let mut results: Vec<SignatureResult> = self.base.clone();
+		for buffer in buffers {
+			results.extend(buffer.iter().cloned());
+		}
+		if g.deterministic {
+			results.sort_by_key(|s| s.index);
+			if g.leading0 {
+				let mut best: Option<u32> = None;
+				results.retain(|s| {
+					let better: bool = best.is_none_or(|best| s.selector < best);
+					if better {
+						best = Some(s.selector);
+					}
+					better
+				});
+			}
+			if let Some(k) = g.min_leading_zero {
+				if let Some(first) = results.iter().position(|s| s.index.is_some() && s.leading_zero >= k) {
+					results.truncate(first + 1);
+				}
+			}
+			results.truncate(g.max_results);
+		} else if g.leading0 {
+			results.sort_by_key(|s| std::cmp::Reverse(s.selector));
+		}
+		results
+	}
+
+
+	/// The first index of the keyspace not searched yet, given out to a thread or not.
+	fn watermark(&self, queue: &Queue) -> IteratedValue {
+		let pending: IteratedValue = queue.pending.iter().map(|&(start, _)| start).min().unwrap_or(queue.next);
+		self.slots.iter()
+			.map(|slot| (slot.at.load(Ordering::Relaxed), slot.end.load(Ordering::Relaxed)))
+			.filter(|&(at, end)| at < end)
+			.fold(pending.min(queue.next), |low, (at, _)| low.min(at))
+	}
+
+
+	/// Checks the goal in deterministic mode : the results before the first index not searched yet are
+	/// settled, nothing can be found before them anymore. The results of the threads that can't be part of
+	/// the results anymore are dropped.
+	fn settle(&self, g: &Globals) {
+		let mut buffers: Vec<MutexGuard<'_, Vec<SignatureResult>>> = self.lock_buffers();
+		let watermark: IteratedValue                                = self.watermark(&self.queue.lock().expect("Mutex panic ! "));
+		let results: Vec<SignatureResult>                           = self.results(g, &buffers);
+
+		let kept: HashSet<IteratedValue> = results.iter().filter_map(|s| s.index).collect();
+		for buffer in buffers.iter_mut() {
+			buffer.retain(|s| s.index.is_some_and(|index| kept.contains(&index)));
+		}
+
+		let settled: Vec<&SignatureResult> = results.iter().take_while(|s| s.index.is_none_or(|index| index < watermark)).collect();
+		if settled.len() >= g.max_results || (g.leading0 && settled.iter().any(|s| s.index.is_some() && s.selector == 0)) {
+			self.finish(StopReason::Goal);
+		} else if g.min_leading_zero.is_some_and(|k| settled.iter().any(|s| s.index.is_some() && s.leading_zero >= k)) {
+			self.finish(StopReason::LeadingZero);
+		}
+	}
+
+
+	/// Gives out the next chunk of the keyspace to a thread, and records it in the slot of the thread.
+	///
+	/// A chunk never spans two passes, the rest of a range goes back to the queue.
+	///
+	/// Returns:
+	///
+	/// The pass of the chunk and the range of its values in the pass, `None` once the keyspace is given out.
+	fn claim<F>(&self, g: &Globals, slot_idx: usize, on_event: &F) -> Option<(u32, IteratedValue, IteratedValue)>
+	where
+		F: Fn(Event) + Sync,
+	{
+		let mut queue: MutexGuard<'_, Queue> = self.queue.lock().expect("Mutex panic ! ");
+		let (start, end): (IteratedValue, IteratedValue) = if !queue.pending.is_empty() {
+			queue.pending.remove(0)
+		} else if queue.next < g.keyspace() {
+			let start: IteratedValue = queue.next;
+			queue.next = (start + CHUNK).min(g.keyspace());
+			(start, queue.next)
+		} else {
+			return None;
+		};
+
+		let (digit, value): (u32, IteratedValue) = g.locate(start);
+		let pass_end: IteratedValue = g.pass_start(digit) + g.pass_len(digit);
+		let end: IteratedValue      = if end > pass_end {
+			queue.pending.insert(0, (pass_end, end));
+			pass_end
+		} else {
+			end
+		};
+
+		let slot: &Slot = &self.slots[slot_idx];
+		slot.at.store(start, Ordering::Relaxed);
+		slot.end.store(end, Ordering::Relaxed);
+
+		if digit > queue.pass {
+			queue.pass = digit;
+			on_event(Event::PassStart(digit));
+		}
+		Some((digit, value, value + end - start))
+	}
+
+
+	/// Reserves the next batch of hashes for a thread, checking the deadline and the hash budget.
+	///
+	/// Returns:
+	///
+	/// The number of hashes the thread can compute, 0 if the search must stop.
+	fn reserve(&self, g: &Globals) -> u64 {
+		if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+			self.finish(StopReason::Deadline);
+			return 0;
+		}
+
+		// Smaller batches near the end of the budget, so the threads share its last hashes
+		let batch: u64 = match self.budget {
+			Some(budget) => {
+				let left: u64 = budget.saturating_sub(self.hashes.load(Ordering::Relaxed));
+				(left / g.nn_threads as u64).clamp(1, HASH_BATCH)
+			}
+			None => HASH_BATCH,
+		};
+
+		let previous: u64 = self.hashes.fetch_add(batch, Ordering::Relaxed);
+		match self.budget {
+			Some(budget) if previous + batch > budget => {
+				let left: u64 = budget.saturating_sub(previous);
+				self.hashes.fetch_sub(batch - left, Ordering::Relaxed);
+				if left == 0 {
+					self.finish(StopReason::HashBudget);
+				}
+				left
+			}
+			_ => batch,
+		}
+	}
+
+
+	/// Computes the statistics of the run, see `Progress`.
+	///
+	/// Arguments:
+	///
+	/// * `g`: The settings of the search.
+	/// * `elapsed`: The duration of the run.
+	/// * `hashes_before`: The number of hashes of the resumed checkpoint, not part of the hash rate.
+	fn progress(&self, g: &Globals, elapsed: Duration, hashes_before: u64) -> Progress {
+		let hashes: u64         = self.hashes.load(Ordering::Relaxed);
+		let hashes_per_sec: f64 = (hashes - hashes_before) as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+		let pass: u32           = self.queue.lock().expect("Mutex panic ! ").pass.max(1);
+		let left: IteratedValue = self.left[pass as usize].load(Ordering::Relaxed);
+
+		Progress {
+			hashes,
+			elapsed,
+			hashes_per_sec,
+			pass,
+			pass_done: 1.0 - left as f64 / g.pass_len(pass) as f64,
+			next_hit : Some(hashes_per_sec * zeros_probability(g.difficulty))
+				.filter(|&hits_per_sec| hits_per_sec > 0.0)
+				.and_then(|hits_per_sec| Duration::try_from_secs_f64(1.0 / hits_per_sec).ok()),
+		}
+	}
+
+
+	/// Takes a snapshot of the run, see `Checkpoint`.
+	fn checkpoint(&self, g: &Globals) -> Checkpoint {
+		let buffers: Vec<MutexGuard<'_, Vec<SignatureResult>>> = self.lock_buffers();
+		let queue: MutexGuard<'_, Queue>                        = self.queue.lock().expect("Mutex panic ! ");
+		let results: Vec<SignatureResult>                       = self.results(g, &buffers);
+
+		let mut pending: Vec<(IteratedValue, IteratedValue)> = queue.pending.clone();
+		for slot in &self.slots {
+			let (at, end) = (slot.at.load(Ordering::Relaxed), slot.end.load(Ordering::Relaxed));
+			if at < end {
+				pending.push((at, end));
+			}
+		}
+		pending.sort_unstable();
+		let first: IteratedValue = pending.first().map_or(queue.next, |&(start, _)| start);
+
+		Checkpoint {
+			signature    : g.template(),
+			difficulty   : g.difficulty,
+			leading0     : g.leading0,
+			max_results  : g.max_results,
+			deterministic: g.deterministic,
+			alphabet     : String::from_utf8_lossy(g.alphabet.chars()).into_owned(),
+			pattern      : g.pattern.as_ref().map_or(String::new(), |pattern| pattern.to_string()),
+			words        : g.pattern.as_ref().map_or(vec![], |pattern| pattern.words().to_vec()),
+			placement    : g.placement,
+			separator    : g.separator.clone(),
+			cases        : g.cases.then(|| g.digit_max - 1),
+			permutation  : g.permutation.as_ref().map(|permutation| permutation.positions().to_vec()),
+			type_suffix  : g.types.as_ref().map(|types| g.digit_max / types.len() as u32 - 1),
+			output       : g.output.clone(),
+			nn_threads   : g.nn_threads,
+			digit        : g.locate(first).0,
+			next         : queue.next,
+			pending,
+			optimal      : results.last().map_or(u32::MAX, |s| s.selector),
+			hashes       : self.hashes.load(Ordering::Relaxed),
+			results,
+		}
+	}
+
+}
+
+
+impl Searcher {
+
+	/// Builds a `Searcher` from a `Globals` configuration.
+	///
+	/// Returns:
+	///
+	/// The `Searcher`, or the `ConfigError` returned by `Globals::validate`.
+	pub fn new(g: Globals) -> Result<Searcher, ConfigError> {
+		g.validate()?;
+		Ok(Searcher {
+			g,
+			stop  : Arc::new(AtomicBool::new(false)),
+			resume: None,
+		})
+	}
+
+
+	/// Builds a `Searcher` that continues a search exactly where its `Checkpoint` stopped.
+	///
+	/// Returns:
+	///
+	/// The `Searcher`, or a `ConfigError` if `g` is invalid or the checkpoint was made with other settings.
+	pub fn resume(g: Globals, checkpoint: Checkpoint) -> Result<Searcher, ConfigError> {
+		let mut searcher: Searcher = Searcher::new(g)?;
+		if !checkpoint.matches(&searcher.g) {
+			return Err(ConfigError::CheckpointMismatch);
+		}
+		searcher.resume = Some(checkpoint);
+		Ok(searcher)
+	}
+
+
+	/// The validated configuration of the search.
+	pub fn globals(&self) -> &Globals {
+		&self.g
+	}
+
+
+	/// A flag to stop the search from another thread, `run` returns the results found so far once set.
+	pub fn stop_handle(&self) -> Arc<AtomicBool> {
+		Arc::clone(&self.stop)
+	}
+
+
+	/// Runs the search.
+	///
+	/// Returns:
+	///
+	/// The results, the initial signature first.
+	pub fn run(&self) -> Vec<SignatureResult> {
+		self.run_with(|_| {}).results
+	}
+
+
+	/// Runs the search, reporting its progress to `on_event`.
+	///
+	/// Launches multiple threads sharing the suffixes of all the passes by chunks, until the goal is
+	/// reached, the passes run out or another stop condition is met. A search stopped before its end
+	/// (see `StopReason::is_resumable`) reports a last `Event::Checkpoint` before returning.
+	///
+	/// Arguments:
+	///
+	/// * `on_event`: Called from the worker threads at each pass and for every new result kept.
+	///
+	/// Returns:
+	///
+	/// The `SearchReport`, the results and why the search stopped.
+	pub fn run_with<F>(&self, on_event: F) -> SearchReport
+	where
+		F: Fn(Event) + Sync,
+	{
+		let g: &Globals = &self.g;
+		let (results, hashes, pending, next) = match &self.resume {
+			Some(cp) => (cp.results.clone(), cp.hashes, cp.pending.clone(), cp.next),
+			None     => (vec![signature_result(&g.signature)], 0, vec![], 0),
+		};
+
+		// Suffixes of each pass not searched yet, those given out before a resumed checkpoint excluded
+		let left: Vec<AtomicIteratedValue> = (0..=g.digit_max).map(|digit| {
+			if digit == 0 {
+				return AtomicIteratedValue::new(0);
+			}
+			let (from, to) = (g.pass_start(digit), g.pass_start(digit) + g.pass_len(digit));
+			let pending_in: IteratedValue = pending.iter()
+				.map(|&(start, end)| end.min(to).saturating_sub(start.max(from)))
+				.sum();
+			AtomicIteratedValue::new(to - next.clamp(from, to) + pending_in)
+		}).collect();
+
+		let shared: Shared = Shared {
+			initial : results.first().map_or(u32::MAX, |s| s.selector),
+			count   : AtomicUsize::new(results.len()),
+			kept    : AtomicUsize::new(results.len()),
+			best    : AtomicU32::new(results.last().map_or(u32::MAX, |s| s.selector)),
+			base    : results,
+			buffers : (0..g.nn_threads).map(|_| Mutex::new(vec![])).collect(),
+			done    : AtomicBool::new(false),
+			reason  : OnceLock::new(),
+			deadline: g.deadline.map(|deadline| Instant::now() + deadline),
+			budget  : g.max_hashes.map(|max_hashes| hashes + max_hashes),
+			hashes  : AtomicU64::new(hashes),
+			queue   : Mutex::new(Queue { pending, next, pass: 0 }),
+			slots   : (0..g.nn_threads).map(|_| Slot { at: AtomicIteratedValue::new(0), end: AtomicIteratedValue::new(0) }).collect(),
+			left,
+		};
+		let finished: AtomicBool = AtomicBool::new(false);
+
+		let templates: Vec<Template> = (1..=g.digit_max)
+			.map(|digit| Template::new(g.head(digit).as_bytes(), g.suffix_len(digit), g.tail(digit).as_bytes()))
+			.collect();
+
+		let _ = thread::scope(|scope| {
+			let _finished: SetOnDrop = SetOnDrop(&finished);
+			if g.checkpoint_every.is_some() || g.progress_every.is_some() {
+				let (shared, finished, on_event) = (&shared, &finished, &on_event);
+				scope.spawn(move |_| {
+					let start: Instant               = Instant::now();
+					let mut last_checkpoint: Instant = start;
+					let mut last_progress: Instant   = start;
+					let tick: Duration               = [g.checkpoint_every, g.progress_every].into_iter().flatten()
+						.fold(Duration::from_millis(100), Duration::min);
+					while !finished.load(Ordering::Relaxed) {
+						std::thread::sleep(tick);
+						if g.checkpoint_every.is_some_and(|every| last_checkpoint.elapsed() >= every) {
+							on_event(Event::Checkpoint(&shared.checkpoint(g)));
+							last_checkpoint = Instant::now();
+						}
+						// Once done, the threads give back the hashes they reserved, the count would go down
+						if g.progress_every.is_some_and(|every| last_progress.elapsed() >= every) && !shared.done.load(Ordering::Relaxed) {
+							on_event(Event::Progress(&shared.progress(g, start.elapsed(), hashes)));
+							last_progress = Instant::now();
+						}
+					}
+				});
+			}
+
+			let workers: Vec<_> = (0..g.nn_threads).map(|slot_idx| {
+				let shared: &Shared          = &shared;
+				let stop: &AtomicBool        = &self.stop;
+				let on_event: &F             = &on_event;
+				let templates: &[Template]   = &templates;
+				scope.spawn(move |_| {
+					thread(g, shared, stop, on_event, templates, slot_idx);
+				})
+			}).collect();
+			for worker in workers {
+				let _ = worker.join();
+			}
+		});
+
+		if self.stop.load(Ordering::Relaxed) {
+			shared.finish(StopReason::Interrupted);
+		}
+		shared.finish(StopReason::AllDone);
+		let stop: StopReason = *shared.reason.get().expect("Stop reason set");
+
+		if stop.is_resumable() {
+			on_event(Event::Checkpoint(&shared.checkpoint(g)));
+		}
+
+		let mut results: Vec<SignatureResult> = shared.results(g, &shared.lock_buffers());
+		rank(g, &mut results);
+		SearchReport {
+			hashes : shared.hashes.load(Ordering::Relaxed),
+			results,
+			stop,
+		}
+	}
+
+}
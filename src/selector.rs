@@ -0,0 +1,147 @@
+use crate::globals::IteratedValue;
+use crate::keccak;
+
+
+pub struct SelectorResult {
+	pub selector    : u32,
+	pub zero_counter: u32,
+}
+
+
+/// The `SignatureResult` struct represents the result of a signature operation, containing a signature
+/// string, a selector value, and a leading zero count.
+///
+/// Properties:
+///
+/// * `signature`: A string that represents a valid Solidity signature.
+/// * `selector`: The `selector` property is of type `u32`, which stands for unsigned 32-bit integer. It
+/// is used to store a numeric value that represents a selector.
+/// * `leading_zero`: The `leading_zero` property is of type `u32`, which stands for unsigned 32-bit
+/// integer. It represents the number of leading zeros in the binary representation of the `signature`
+/// property.
+/// * `nbr_of_zero`: The number of zero bytes in the selector.
+/// * `index`: The index of the suffix in the keyspace, see `Globals::keyspace`, `None` for the initial
+/// signature.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SignatureResult {
+	pub signature   : String,
+	pub selector    : u32,
+	pub leading_zero: u32,
+	pub nbr_of_zero : u32,
+	pub index       : Option<IteratedValue>,
+}
+
+
+/// The function takes a signature as input, hashes it using Keccak-256, and converts the first 4 bytes
+/// of the hash into a selector, counting its number of zero bytes.
+///
+/// Arguments:
+///
+/// * `signature`: The `signature` parameter is a string that represents a function signature. It is
+/// used to generate a selector, which is a unique identifier for the function.
+///
+/// Returns:
+///
+/// The function `signature_to_selector` returns a `SelectorResult` struct.
+pub fn signature_to_selector(signature: &str) -> SelectorResult {
+	let selector_u32: u32 = keccak::selector(signature.as_bytes());
+
+	SelectorResult {
+		selector    : selector_u32,
+		zero_counter: count_zeros(selector_u32),
+	}
+}
+
+
+/// The function counts the number of zero bytes in a 32-bit unsigned integer.
+///
+/// Arguments:
+///
+/// * `selector_u32`: The parameter `selector_u32` is an unsigned 32-bit integer.
+///
+/// Returns:
+///
+/// The number of zero bytes, from 0 to 4.
+#[inline]
+pub fn count_zeros(selector_u32: u32) -> u32 {
+	selector_u32.to_be_bytes().iter().filter(|&&vu8| vu8 == 0).count() as u32
+}
+
+
+/// The function counts the number of leading zeros in a 32-bit unsigned integer.
+///
+/// Arguments:
+///
+/// * `selector_u32`: The parameter `selector_u32` is an unsigned 32-bit integer.
+///
+/// Returns:
+///
+/// If none of the conditions in the if statements are true, then the function will return 4.
+pub fn count_leading_zeros(selector_u32: u32) -> u32 {
+	if (selector_u32 & 0xFF000000) != 0 { return 0;}
+	if (selector_u32 & 0x00FF0000) != 0 { return 1;}
+	if (selector_u32 & 0x0000FF00) != 0 { return 2;}
+	if (selector_u32 & 0x000000FF) != 0 { return 3;}
+	4
+}
+
+
+/// The probability that a selector has at least `nn_zeros` zero bytes, each of its 4 bytes being zero
+/// with a 1/256 probability (binomial distribution).
+///
+/// Arguments:
+///
+/// * `nn_zeros`: The minimal number of zero bytes, from 0 to 4.
+///
+/// Returns:
+///
+/// The probability, e.g. about 1/65536 for `nn_zeros` = 2.
+pub fn zeros_probability(nn_zeros: u32) -> f64 {
+	let p: f64 = 1.0 / 256.0;
+	(nn_zeros.min(4)..=4)
+		.map(|k| binomial(4, k) * p.powi(k as i32) * (1.0 - p).powi(4 - k as i32))
+		.sum()
+}
+
+
+/// The probability that a selector has at least `nn_zeros` leading zero bytes.
+///
+/// Arguments:
+///
+/// * `nn_zeros`: The minimal number of leading zero bytes, from 0 to 4.
+///
+/// Returns:
+///
+/// The probability, 1/256 for each leading zero byte.
+pub fn leading_zeros_probability(nn_zeros: u32) -> f64 {
+	(1.0 / 256f64).powi(nn_zeros.min(4) as i32)
+}
+
+
+/// The number of ways to choose `k` items among `n`.
+pub(crate) fn binomial(n: u32, k: u32) -> f64 {
+	(0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+
+/// Computes the `SignatureResult` of a signature, whatever its number of zeros.
+///
+/// Arguments:
+///
+/// * `signature`: A valid Solidity signature.
+///
+/// Returns:
+///
+/// The `SignatureResult` of the signature.
+pub fn signature_result(signature: &str) -> SignatureResult {
+	let s2s: SelectorResult = signature_to_selector(signature);
+
+	SignatureResult {
+		signature   : signature.to_string(),
+		selector    : s2s.selector,
+		leading_zero: count_leading_zeros(s2s.selector),
+		nbr_of_zero : s2s.zero_counter,
+		index       : None,
+	}
+}
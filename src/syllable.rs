@@ -0,0 +1,37 @@
+/// The consonants of the default syllables, those read the same way in most languages.
+pub const CONSONANTS: [&str; 15] = ["b", "d", "f", "g", "h", "k", "l", "m", "n", "p", "r", "s", "t", "v", "z"];
+
+/// The vowels of the default syllables.
+pub const VOWELS: [&str; 5] = ["a", "e", "i", "o", "u"];
+
+
+/// Adds the default syllable tables to word lists, those with the same names are replaced by the lists.
+///
+/// The tables are the lists `consonant` and `vowel`, and `syllable`, every consonant followed by every
+/// vowel, e.g. `ka` or `bo`. A pattern builds pronounceable names from them, e.g. `{syllable}{2}` gives
+/// `kabo`, `{syllable}{2}{consonant}` gives `zulef`. A syllable table built from replaced consonants or
+/// vowels is built from the replacements.
+///
+/// Arguments:
+///
+/// * `words`: The word lists, by name.
+///
+/// Returns:
+///
+/// The word lists, followed by the default tables not replaced.
+pub fn with_tables(words: &[(String, Vec<String>)]) -> Vec<(String, Vec<String>)> {
+	let find = |name: &str| words.iter().find(|(list, _)| list.eq_ignore_ascii_case(name)).map(|(_, list)| list.clone());
+	let consonants: Vec<String> = find("consonant").unwrap_or_else(|| CONSONANTS.iter().map(|c| c.to_string()).collect());
+	let vowels: Vec<String>     = find("vowel").unwrap_or_else(|| VOWELS.iter().map(|v| v.to_string()).collect());
+	let syllables: Vec<String>  = find("syllable").unwrap_or_else(|| {
+		consonants.iter().flat_map(|c| vowels.iter().map(move |v| format!("{}{}", c, v))).collect()
+	});
+
+	let mut tables: Vec<(String, Vec<String>)> = words.to_vec();
+	for (name, list) in [("consonant", consonants), ("vowel", vowels), ("syllable", syllables)] {
+		if find(name).is_none() {
+			tables.push((name.to_string(), list));
+		}
+	}
+	tables
+}
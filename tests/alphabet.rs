@@ -0,0 +1,72 @@
+use select0r::selector::signature_result;
+use select0r::{Alphabet, ConfigError, Globals, SearchReport, Searcher, StopReason};
+
+
+#[test]
+fn presets() {
+	assert_eq!(Alphabet::Base64.len(), 64);
+	assert_eq!(Alphabet::Hex.len(), 16);
+	assert_eq!(Alphabet::Lowercase.len(), 26);
+	assert_eq!(Alphabet::Alnum.len(), 62);
+	assert_eq!(Alphabet::NoDollar.len(), 63);
+	for preset in Alphabet::PRESETS {
+		assert_eq!(preset.validate(), Ok(()));
+		assert_eq!(preset.name().parse::<Alphabet>(), Ok(preset.clone()));
+		assert_eq!(Alphabet::from_chars(std::str::from_utf8(preset.chars()).unwrap()), preset);
+	}
+}
+
+
+#[test]
+fn passes_fit() {
+	assert_eq!(Alphabet::Base64.max_digits(), 10);
+	assert_eq!(Alphabet::Hex.max_digits(), 15);
+	for chars in ["01", "abc", "0123456789", "xyz_$"] {
+		let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+		g.set_alphabet(Alphabet::custom(chars).unwrap());
+		let keyspace: u128 = (1..=g.digit_max).map(|d| (chars.len() as u128).pow(d)).sum();
+		assert_eq!(g.keyspace() as u128, keyspace);
+		assert!(keyspace * chars.len() as u128 > u64::MAX as u128, "{}", chars);
+	}
+}
+
+
+#[test]
+fn invalid_custom() {
+	for chars in ["", "a", "aba", "ab-", "abé", "a b"] {
+		assert_eq!(Alphabet::custom(chars), Err(ConfigError::InvalidAlphabet(chars.to_string())), "{}", chars);
+	}
+
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.alphabet = Alphabet::Custom("aa".to_string());
+	assert!(Searcher::new(g.clone()).is_err());
+	g.set_alphabet(Alphabet::Hex);
+	g.digit_max = 16;
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::InvalidDigitMax(16)));
+}
+
+
+#[test]
+fn suffixes_of_the_alphabet() {
+	for alphabet in [Alphabet::Hex, Alphabet::Lowercase, Alphabet::custom("Q7_").unwrap()] {
+		let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+		g.difficulty    = 1;
+		g.nn_threads    = 3;
+		g.max_results   = 12;
+		g.deterministic = true;
+		g.set_alphabet(alphabet.clone());
+
+		let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+		assert_eq!(report.stop, StopReason::Goal);
+		for res in &report.results[1..] {
+			let suffix: &str = res.signature.trim_start_matches("deposit_").trim_end_matches("(uint256)");
+			assert!(suffix.bytes().all(|c| alphabet.chars().contains(&c)), "{}", res.signature);
+			assert_eq!(res.selector, signature_result(&res.signature).selector);
+		}
+
+		// In keyspace order, the shortest suffixes first
+		for pair in report.results[1..].windows(2) {
+			assert!(pair[0].signature.len() <= pair[1].signature.len());
+		}
+	}
+}
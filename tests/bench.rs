@@ -0,0 +1,20 @@
+use select0r::bench::{self, BenchResult};
+use select0r::Backend;
+
+
+#[test]
+fn every_thread_count() {
+	let results: Vec<BenchResult> = bench::run("bench(uint256)", 20_000, &[Backend::Scalar], 2, |_| {}).unwrap();
+	assert_eq!(results.len(), 2);
+	assert_eq!(results.iter().map(|res| res.threads).collect::<Vec<usize>>(), vec![1, 2]);
+	// The hash budget is shared by batches, several threads may stop a bit before it
+	assert!(results.iter().all(|res| (19_000..=20_000).contains(&res.hashes) && res.backend == Backend::Scalar));
+	assert_eq!(results[0].efficiency, 1.0);
+	assert!(bench::best(&results).is_some());
+}
+
+
+#[test]
+fn invalid_signature() {
+	assert!(bench::run("bench", 1_000, &[Backend::Scalar], 1, |_| {}).is_err());
+}
@@ -0,0 +1,101 @@
+use select0r::selector::signature_result;
+use select0r::{Alphabet, Checkpoint, ConfigError, Globals, Pattern, SearchReport, Searcher, StopReason};
+
+mod common;
+
+
+fn search(suffix_passes: u32) -> SearchReport {
+	let mut g: Globals = Globals::new("transferFrom(address,address,uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = 10;
+	g.deterministic = true;
+	g.set_alphabet(Alphabet::Hex);
+	g.set_cases(suffix_passes);
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	assert_eq!(report.results[0].signature, "transferFrom(address,address,uint256)");
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn variants_of_the_name() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.set_cases(0);
+	assert_eq!(g.digit_max, 1);
+	assert_eq!(g.keyspace(), 1 << 7);
+	assert_eq!(g.suffix_len(1), "deposit".len());
+
+	// Only the cases change, never the original name
+	let report: SearchReport = search(0);
+	for res in &report.results[1..] {
+		let name: &str = res.signature.strip_suffix("(address,address,uint256)").unwrap();
+		assert!(name.eq_ignore_ascii_case("transferFrom") && name != "transferFrom", "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn variants_with_a_suffix() {
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.set_alphabet(Alphabet::Hex);
+	g.set_cases(2);
+	assert_eq!(g.keyspace(), 16 + 16 * 16 + 16 * 16 * 16);
+	assert_eq!(g.suffix_len(2), "mint_7".len());
+
+	let report: SearchReport = search(1);
+	for res in &report.results[1..] {
+		let name: &str = res.signature.strip_suffix("(address,address,uint256)").unwrap();
+		let base: &str = name.split('_').next().unwrap();
+		assert!(base.eq_ignore_ascii_case("transferFrom"), "{}", res.signature);
+		assert!(name == base || name[base.len() + 1..].chars().all(|c| c.is_ascii_hexdigit()), "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn resumed_cases() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 10;
+	g.deterministic = true;
+	g.set_alphabet(Alphabet::Hex);
+	g.set_cases(1);
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.cases, Some(1));
+
+	g.set_cases(2);
+	assert!(!cp.matches(&g));
+}
+
+
+#[test]
+fn too_many_variants() {
+	// 2^60 variants of the name, then 64 suffixes of each
+	let mut g: Globals = Globals::new(&format!("{}(uint256)", "a".repeat(60))).unwrap();
+	g.set_cases(0);
+	assert!(Searcher::new(g.clone()).is_ok());
+	g.set_cases(1);
+	assert_eq!(Searcher::new(g.clone()).err(), Some(ConfigError::InvalidDigitMax(2)));
+
+	// At most the 15 passes of `hex`, then 16 variants of 16^15 suffixes
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.set_alphabet(Alphabet::Hex);
+	g.set_cases(20);
+	assert_eq!(g.digit_max, 16);
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::InvalidDigitMax(16)));
+
+	// At most the passes of the pattern
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.set_pattern(Pattern::new("[a-z]").unwrap());
+	g.set_cases(5);
+	assert_eq!(g.digit_max, 2);
+	assert_eq!(g.keyspace(), 16 + 16 * 26);
+	assert!(Searcher::new(g).is_ok());
+}
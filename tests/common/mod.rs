@@ -0,0 +1,58 @@
+use select0r::{Checkpoint, Event, Globals, IteratedValue, SearchReport, Searcher, StopReason};
+use std::sync::Mutex;
+
+
+/// Runs a search to its end.
+///
+/// Returns:
+///
+/// The report of the search and its last checkpoint, if any.
+pub fn run(searcher: Searcher) -> (SearchReport, Option<Checkpoint>) {
+	let last: Mutex<Option<Checkpoint>> = Mutex::new(None);
+	let report: SearchReport            = searcher.run_with(|event| {
+		if let Event::Checkpoint(cp) = event {
+			*last.lock().unwrap() = Some(cp.clone());
+		}
+	});
+	(report, last.into_inner().unwrap())
+}
+
+
+/// Searches the deterministic `g` at once, then stopped half way and resumed from its checkpoint by
+/// another number of threads, and checks both searches find the same results in the same order, no gap,
+/// no duplicate.
+///
+/// Arguments:
+///
+/// * `g`: The searched globals, reaching `max_results` in a few thousand hashes.
+///
+/// Returns:
+///
+/// The checkpoint of the interrupted search, read back from its text.
+pub fn resumed_same_as_uninterrupted(g: &Globals) -> Checkpoint {
+	let mut g: Globals = g.clone();
+	g.nn_threads       = 2;
+	g.max_hashes       = None;
+	let (whole, _)     = run(Searcher::new(g.clone()).unwrap());
+	assert_eq!(whole.stop, StopReason::Goal);
+	assert!(whole.results.len() > 1);
+
+	g.nn_threads       = 3;
+	g.max_hashes       = Some(whole.hashes / 2);
+	let (first, cp)    = run(Searcher::new(g.clone()).unwrap());
+	assert_eq!(first.stop, StopReason::HashBudget);
+	let cp: Checkpoint = cp.unwrap().to_string().parse().unwrap();
+	assert!(cp.matches(&g));
+
+	g.nn_threads       = 1;
+	g.max_hashes       = None;
+	let (last, _)      = run(Searcher::resume(g, cp.clone()).unwrap());
+	assert_eq!(last.stop, StopReason::Goal);
+	assert_eq!(ordered(&last), ordered(&whole));
+	cp
+}
+
+
+fn ordered(report: &SearchReport) -> Vec<(Option<IteratedValue>, String)> {
+	report.results.iter().map(|res| (res.index, res.signature.clone())).collect()
+}
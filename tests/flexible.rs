@@ -0,0 +1,145 @@
+use select0r::selector::signature_result;
+use select0r::{Alphabet, Checkpoint, ConfigError, FlexibleTypes, Globals, Pattern, SearchReport, Searcher, StopReason};
+use std::collections::HashSet;
+
+mod common;
+
+
+const SIGNATURE: &str = "stake(uint{256|128|96|64},{address|uint160|bytes20},int{256|128|64})";
+
+
+fn search(signature: &str, type_suffix: u32, max_results: usize) -> SearchReport {
+	let mut g: Globals = Globals::new(signature).unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = max_results;
+	g.deterministic = true;
+	g.set_alphabet(Alphabet::Hex);
+	g.set_type_suffix(type_suffix);
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.results[0].signature, "stake(uint256,address,int256)");
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn combinations() {
+	let types: FlexibleTypes    = FlexibleTypes::new("(uint{256|96},{address|bytes20})").unwrap();
+	let combinations: Vec<&str> = (0..types.len()).map(|i| types.combination(i)).collect();
+	assert_eq!(combinations, ["(uint256,address)", "(uint256,bytes20)", "(uint96,address)", "(uint96,bytes20)"]);
+
+	let mut g: Globals = Globals::new("transfer{}(uint{256|96},{address|bytes20})").unwrap();
+	assert_eq!(g.signature, "transfer(uint256,address)");
+	assert_eq!(g.template(), "transfer{}(uint{256|96},{address|bytes20})");
+	assert_eq!(g.keyspace(), 4);
+	assert_eq!(g.head(1), "transfer");
+	assert_eq!(g.tail(4), "(uint96,bytes20)");
+
+	// Every combination without suffix, then with the suffixes of each pass
+	g.set_alphabet(Alphabet::Hex);
+	g.set_type_suffix(2);
+	assert_eq!(g.keyspace(), 4 * (1 + 16 + 16 * 16));
+	assert_eq!(g.generated_pass(5), 1);
+	assert_eq!(g.tail(6), "(uint256,bytes20)");
+}
+
+
+#[test]
+fn searched_types() {
+	// The name untouched, each combination once, the initial one apart
+	let report: SearchReport = search(SIGNATURE, 0, 20);
+	assert_eq!(report.stop, StopReason::AllDone);
+	let types: FlexibleTypes = FlexibleTypes::new(&SIGNATURE[5..]).unwrap();
+	let all: HashSet<String> = (1..types.len()).map(|i| format!("stake{}", types.combination(i))).collect();
+	let found: HashSet<&str> = report.results[1..].iter().map(|res| res.signature.as_str()).collect();
+	assert_eq!(found.len(), report.results.len() - 1);
+	assert!(found.iter().all(|signature| all.contains(*signature)), "{:?}", found);
+
+	// With suffixes
+	let report: SearchReport = search(SIGNATURE, 2, 10);
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results[1..] {
+		let (name, args): (&str, &str) = res.signature.split_at(res.signature.find('(').unwrap());
+		let suffix: &str               = name.strip_prefix("stake").unwrap().trim_start_matches('_');
+		assert!((0..types.len()).any(|i| types.combination(i) == args), "{}", res.signature);
+		assert!(suffix.len() <= 2 && suffix.chars().all(|c| c.is_ascii_hexdigit()), "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn encodings() {
+	let types: FlexibleTypes = FlexibleTypes::new("(uint{256|96|128},{uint256|bytes32|bytes|uint256[2]},address)").unwrap();
+	assert_eq!(types.encoding("(uint256,uint256,address)"), "unchanged");
+	assert_eq!(types.encoding("(uint96,uint256,address)"), "#1 uint96: same size, reverts out of range");
+	assert_eq!(types.encoding("(uint256,bytes32,address)"), "#2 bytes32: same size, left-aligned instead of right-aligned");
+	assert_eq!(types.encoding("(uint256,bytes,address)"), "#2 bytes: dynamic, an offset in place and the data at the end");
+	assert_eq!(
+		types.encoding("(uint128,uint256[2],address)"),
+		"#1 uint128: same size, reverts out of range; #2 uint256[2]: 64 bytes in place instead of 32",
+	);
+}
+
+
+#[test]
+fn invalid_types() {
+	for signature in ["f(uint{96|96})", "f(uint{96|128)", "f(uint{96|})", "f(uint{96|1 28})", "f(uint96})"] {
+		assert_eq!(Globals::new(signature).err(), Some(ConfigError::InvalidTypes(signature[1..].to_string())), "{}", signature);
+	}
+	let too_many: String = format!("f({})", ["uint{8|16|32|64}"; 6].join(","));
+	assert!(matches!(Globals::new(&too_many).err(), Some(ConfigError::InvalidTypes(_))));
+
+	// Not with the case mode
+	let mut g: Globals = Globals::new("f(uint{256|96})").unwrap();
+	g.set_cases(0);
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::InvalidTypes("(uint{256|96})".to_string())));
+}
+
+
+#[test]
+fn suffix_passes() {
+	// At most the passes of the pattern
+	let mut g: Globals = Globals::new("x(uint{256|128})").unwrap();
+	g.set_pattern(Pattern::new("[a-z]{1,3}").unwrap());
+	g.set_type_suffix(5);
+	assert_eq!(g.digit_max, (3 + 1) * 2);
+	assert_eq!(g.keyspace(), 2 * (1 + 26 + 26 * 26 + 26 * 26 * 26));
+	assert!(Searcher::new(g).is_ok());
+
+	// Or of the alphabet, then too many suffixes for the combinations
+	let mut g: Globals = Globals::new("x(uint{8|16|32|64},int{8|16|32|64})").unwrap();
+	g.set_alphabet(Alphabet::Hex);
+	g.set_type_suffix(20);
+	assert_eq!(g.digit_max, 16 * 16);
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::InvalidDigitMax(256)));
+
+	// Whatever the order of the setters
+	let mut g: Globals = Globals::new("x(uint{256|128})").unwrap();
+	g.set_type_suffix(5);
+	g.set_pattern(Pattern::new("[a-z]{1,3}").unwrap());
+	assert_eq!(g.digit_max, (3 + 1) * 2);
+	g.set_alphabet(Alphabet::Hex);
+	assert_eq!(g.digit_max, (3 + 1) * 2);
+	assert!(Searcher::new(g).is_ok());
+}
+
+
+#[test]
+fn resumed_types() {
+	let mut g: Globals = Globals::new(SIGNATURE).unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 10;
+	g.deterministic = true;
+	g.set_type_suffix(1);
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.signature, SIGNATURE);
+	assert_eq!(cp.type_suffix, Some(1));
+
+	g.set_type_suffix(2);
+	assert!(!cp.matches(&g));
+}
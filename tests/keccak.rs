@@ -0,0 +1,108 @@
+extern crate crypto;
+
+use crypto::digest::Digest;
+use crypto::sha3::Sha3;
+
+use select0r::keccak;
+
+
+/// Selector computed by the former, general-purpose implementation.
+fn reference_selector(message: &[u8]) -> u32 {
+	let mut hasher: Sha3 = Sha3::keccak256();
+	hasher.input(message);
+	let mut hash: [u8; 32] = [0; 32];
+	hasher.result(&mut hash);
+	u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+
+#[test]
+fn known_selectors() {
+	assert_eq!(keccak::selector(b"mint(uint256)"),    0xa0712d68);
+	assert_eq!(keccak::selector(b"deposit(uint256)"), 0xb6b55f25);
+	assert_eq!(keccak::selector(b"execute_6d4S()"),   0x000000ae);
+	assert_eq!(keccak::selector(b""),                 0xc5d24601);
+}
+
+
+#[test]
+fn same_as_reference_for_every_length() {
+	// Around the block size, the padding byte and the last rate byte may be the same byte
+	for len in 0..=3 * keccak::RATE + 1 {
+		let message: Vec<u8> = (0..len).map(|i| b"abcdefghijklmnopqrstuvwxyz_$()0123456789"[i % 40]).collect();
+		assert_eq!(keccak::selector(&message), reference_selector(&message), "length {}", len);
+	}
+}
+
+
+#[test]
+fn same_as_reference_for_candidates() {
+	let alphabet: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+	for i in 0..64 * 64 {
+		let signature: String = format!("transferFrom_{}{}(address,address,uint256)",
+			alphabet[i / 64] as char, alphabet[i % 64] as char);
+		assert_eq!(keccak::selector(signature.as_bytes()), reference_selector(signature.as_bytes()), "{}", signature);
+	}
+}
+
+
+#[test]
+fn template_same_as_selector() {
+	// Suffixes across lane boundaries, and messages too long for a single block
+	for prefix_len in [0, 3, 7, 8, 13, 60, 120, 130, 140] {
+		let prefix: String = "p".repeat(prefix_len) + "_";
+		for suffix_len in 1..=10 {
+			let template: keccak::Template = keccak::Template::new(prefix.as_bytes(), suffix_len, b"(uint256)");
+			for k in 0..50u8 {
+				let suffix: Vec<u8> = (0..suffix_len as u8).map(|i| b'0' + (k + 7 * i) % 75).collect();
+				let message: Vec<u8> = [prefix.as_bytes(), &suffix, b"(uint256)"].concat();
+				assert_eq!(template.selector(&suffix), keccak::selector(&message), "{:?}", String::from_utf8_lossy(&message));
+			}
+		}
+	}
+}
+
+
+#[test]
+fn batch_same_as_selector() {
+	for prefix_len in [0, 7, 60, 140] {
+		let prefix: String = "p".repeat(prefix_len) + "_";
+		for suffix_len in 1..=10 {
+			let template: keccak::Template = keccak::Template::new(prefix.as_bytes(), suffix_len, b"(uint256)");
+			let suffixes: Vec<Vec<u8>> = (0..8u8)
+				.map(|k| (0..suffix_len as u8).map(|i| b'0' + (k + 5 * i) % 75).collect())
+				.collect();
+			let x4: [u32; 4] = template.selectors([&suffixes[0], &suffixes[1], &suffixes[2], &suffixes[3]].map(|s| s.as_slice()));
+			let x8: [u32; 8] = template.selectors(std::array::from_fn(|k| suffixes[k].as_slice()));
+			for (k, suffix) in suffixes.iter().enumerate() {
+				let selector: u32 = template.selector(suffix);
+				assert_eq!(x8[k], selector, "{} {:?}", prefix_len, String::from_utf8_lossy(suffix));
+				if k < 4 {
+					assert_eq!(x4[k], selector, "{} {:?}", prefix_len, String::from_utf8_lossy(suffix));
+				}
+			}
+		}
+	}
+}
+
+
+#[test]
+fn every_backend_same_as_reference() {
+	let alphabet: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+	// Not a multiple of any number of lanes, to also hash a partial batch
+	let suffixes: Vec<[u8; 2]> = (0..64 * 64 - 3).map(|i| [alphabet[i / 64], alphabet[i % 64]]).collect();
+	let suffixes: Vec<&[u8]>   = suffixes.iter().map(|s| s.as_slice()).collect();
+
+	// A single block template, and one too long for a single block
+	for prefix in ["transferFrom_".to_string(), "t".repeat(140) + "_"] {
+		let template: keccak::Template = keccak::Template::new(prefix.as_bytes(), 2, b"(address,address,uint256)");
+		for backend in keccak::Backend::available() {
+			let mut selectors: Vec<u32> = vec![0; suffixes.len()];
+			template.selectors_with(backend, &suffixes, &mut selectors);
+			for (suffix, selector) in suffixes.iter().zip(&selectors) {
+				let signature: Vec<u8> = [prefix.as_bytes(), suffix, b"(address,address,uint256)"].concat();
+				assert_eq!(*selector, reference_selector(&signature), "{} {:?}", backend, String::from_utf8_lossy(&signature));
+			}
+		}
+	}
+}
@@ -0,0 +1,93 @@
+use select0r::selector::signature_result;
+use select0r::{Checkpoint, ConfigError, Globals, Pattern, Placement, SearchReport, Searcher, StopReason};
+
+mod common;
+
+
+fn search(pattern: &str, nn_threads: usize) -> SearchReport {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = nn_threads;
+	g.max_results   = 30;
+	g.deterministic = true;
+	g.set_pattern(pattern.parse().unwrap());
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn expansions() {
+	let pattern: Pattern = Pattern::new("v[0-9][a-z]{2}").unwrap();
+	assert_eq!(pattern.passes(), 1);
+	assert_eq!(pattern.classes(1).iter().map(|class| class.len()).collect::<Vec<usize>>(), [1, 10, 26, 26]);
+
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.set_pattern(Pattern::new("[A-Z][a-z0-9]{0,3}").unwrap());
+	assert_eq!(g.digit_max, 4);
+	for digit in 1..=4 {
+		assert_eq!(g.classes(digit).len(), digit as usize);
+		assert_eq!(g.pass_len(digit), 26 * 36u64.pow(digit - 1));
+	}
+
+	// The shortest passes first, then by counts
+	let pattern: Pattern = Pattern::new("[ab]{1,2}[0-1]{1,2}").unwrap();
+	let passes: Vec<String> = (1..=pattern.passes())
+		.map(|pass| String::from_utf8(pattern.classes(pass).iter().flat_map(|class| class.token(0)).copied().collect()).unwrap())
+		.collect();
+	assert_eq!(passes, ["a0", "a00", "aa0", "aa00"]);
+}
+
+
+#[test]
+fn invalid_patterns() {
+	for pattern in ["", "[a-", "[z-a]", "[]", "a{2,1}", "a{x}", "a-b", "[a]{0}", "a{0,2000}", "[a-z]{20}", "é"] {
+		assert_eq!(Pattern::new(pattern).err(), Some(ConfigError::InvalidPattern(pattern.to_string())), "{}", pattern);
+	}
+
+	// The generated characters start the name, but some pass can only start with a digit
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.placement = Placement::Prefix;
+	g.set_pattern(Pattern::new("[0-9a]{0,1}[0-9]").unwrap());
+	assert_eq!(Searcher::new(g.clone()).err(), Some(ConfigError::NoLeadingChar("[0-9a]{0,1}[0-9]".to_string())));
+	g.set_pattern(Pattern::new("[0-9a][0-9]").unwrap());
+	assert_eq!(g.pass_len(1), 10);
+	assert!(Searcher::new(g).is_ok());
+}
+
+
+#[test]
+fn suffixes_of_the_pattern() {
+	let report: SearchReport = search("v[0-9][a-z]{2}", 2);
+	for res in &report.results[1..] {
+		let suffix: &[u8] = res.signature.strip_prefix("deposit_v").unwrap().strip_suffix("(uint256)").unwrap().as_bytes();
+		assert!(suffix.len() == 3 && suffix[0].is_ascii_digit() && suffix[1..].iter().all(u8::is_ascii_lowercase), "{}", res.signature);
+	}
+
+	let signatures = |report: &SearchReport| report.results.iter().map(|res| res.signature.clone()).collect::<Vec<String>>();
+	let report: SearchReport = search("[A-Z][a-z0-9]{0,3}", 1);
+	assert_eq!(signatures(&report), signatures(&search("[A-Z][a-z0-9]{0,3}", 3)));
+	let indexes: Vec<u64> = report.results[1..].iter().map(|res| res.index.unwrap()).collect();
+	assert!(indexes.windows(2).all(|w| w[0] < w[1]));
+}
+
+
+#[test]
+fn resumed_pattern() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 20;
+	g.deterministic = true;
+	g.set_pattern(Pattern::new("[a-z]{2,4}").unwrap());
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.pattern, "[a-z]{2,4}");
+
+	g.set_pattern(Pattern::new("[a-z]{2,5}").unwrap());
+	assert!(!cp.matches(&g));
+}
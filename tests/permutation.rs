@@ -0,0 +1,106 @@
+use select0r::permutation::split_arguments;
+use select0r::selector::signature_result;
+use select0r::{Checkpoint, ConfigError, Globals, Permutation, SearchReport, Searcher, StopReason};
+use std::collections::HashSet;
+
+mod common;
+
+
+fn search(signature: &str, positions: &[usize], max_results: usize) -> SearchReport {
+	let mut g: Globals = Globals::new(signature).unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = max_results;
+	g.deterministic = true;
+	g.set_permutation(Permutation::new(&g.part_args, positions).unwrap());
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.results[0].signature, signature);
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn orders() {
+	assert_eq!(split_arguments("(uint256,(address,bool)[],bytes)"), ["uint256", "(address,bool)[]", "bytes"]);
+	assert!(split_arguments("()").is_empty());
+
+	let permutation: Permutation = Permutation::new("(a,b,c)", &[]).unwrap();
+	assert_eq!(permutation.len(), 6);
+	let orders: Vec<String> = (0..permutation.len()).map(|value| Permutation::describe(&permutation.order(value))).collect();
+	assert_eq!(orders, ["1,2,3", "1,3,2", "2,1,3", "2,3,1", "3,1,2", "3,2,1"]);
+
+	// Only the given positions move
+	let permutation: Permutation = Permutation::new("(a,b,c,d)", &[3, 1]).unwrap();
+	assert_eq!(permutation.len(), 2);
+	assert_eq!(permutation.order(1), [0, 3, 2, 1]);
+}
+
+
+#[test]
+fn permuted_arguments() {
+	let signature: &str      = "settle(address,uint256,bytes32,bool,uint64,int8,bytes)";
+	let report: SearchReport = search(signature, &[], 10);
+	assert_eq!(report.stop, StopReason::Goal);
+
+	let sorted = |signature: &str| {
+		let args: &str                 = signature.strip_prefix("settle").unwrap();
+		let mut arguments: Vec<String> = split_arguments(args).into_iter().map(str::to_string).collect();
+		arguments.sort_unstable();
+		arguments
+	};
+	let signatures: HashSet<&str> = report.results.iter().map(|res| res.signature.as_str()).collect();
+	assert_eq!(signatures.len(), report.results.len());
+	for res in &report.results[1..] {
+		assert!(res.signature != signature && sorted(&res.signature) == sorted(signature), "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn same_types_once() {
+	// Swapping the two `uint256` gives the same signature, only 2 other orders are different
+	let report: SearchReport = search("f(uint256,uint256,address)", &[], 20);
+	assert_eq!(report.stop, StopReason::AllDone);
+	let signatures: HashSet<&str> = report.results.iter().map(|res| res.signature.as_str()).collect();
+	assert_eq!(signatures.len(), report.results.len());
+	assert!(report.results.len() <= 3);
+
+	// The others stay in place
+	for res in &search("g(address,uint256,bool,bytes32)", &[1, 3], 20).results[1..] {
+		assert_eq!(res.signature, "g(address,bytes32,bool,uint256)");
+	}
+}
+
+
+#[test]
+fn invalid_permutations() {
+	for positions in [&[1][..], &[0, 4], &[2, 2]] {
+		assert_eq!(Permutation::new("(a,b,c,d)", positions).err(), Some(ConfigError::InvalidPermutation("(a,b,c,d)".to_string())));
+	}
+	assert_eq!(Permutation::new("(uint256)", &[]).err(), Some(ConfigError::InvalidPermutation("(uint256)".to_string())));
+
+	// The arguments of another signature
+	let mut g: Globals = Globals::new("deposit(uint256,address)").unwrap();
+	g.set_permutation(Permutation::new("(uint256,bool)", &[]).unwrap());
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::InvalidPermutation("(uint256,address)".to_string())));
+}
+
+
+#[test]
+fn resumed_permutation() {
+	let mut g: Globals = Globals::new("deposit(uint256,address,bool,bytes32,uint8,uint16,uint32)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 10;
+	g.deterministic = true;
+	g.set_permutation(Permutation::new(&g.part_args, &[]).unwrap());
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.permutation, Some((0..7).collect()));
+
+	g.set_permutation(Permutation::new(&g.part_args, &[0, 2, 4, 6]).unwrap());
+	assert!(!cp.matches(&g));
+}
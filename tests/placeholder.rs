@@ -0,0 +1,91 @@
+use select0r::selector::signature_result;
+use select0r::{Alphabet, Checkpoint, ConfigError, Globals, Placement, SearchReport, Searcher, StopReason};
+
+mod common;
+
+
+fn search(signature: &str, alphabet: Alphabet) -> SearchReport {
+	let mut g: Globals = Globals::new(signature).unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = 40;
+	g.deterministic = true;
+	g.set_alphabet(alphabet);
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn inside_the_name() {
+	let report: SearchReport = search("transfer{}From(address,address,uint256)", Alphabet::Base64);
+	assert_eq!(report.results[0].signature, "transferFrom(address,address,uint256)");
+	for res in &report.results[1..] {
+		let generated: &str = res.signature
+			.strip_prefix("transfer").and_then(|s| s.strip_suffix("From(address,address,uint256)"))
+			.unwrap_or_else(|| panic!("{}", res.signature));
+		assert!(!generated.is_empty() && generated.bytes().all(|c| Alphabet::Base64.chars().contains(&c)), "{}", res.signature);
+	}
+
+	// The placement and the separator are ignored
+	let mut g: Globals = Globals::new("transfer{}From(address,address,uint256)").unwrap();
+	g.placement = Placement::Prefix;
+	g.separator = "__".to_string();
+	assert_eq!(g.head(1), "transfer");
+	assert_eq!(g.tail(1), "From(address,address,uint256)");
+	assert_eq!(g.pass_len(2), 64 * 64);
+}
+
+
+#[test]
+fn at_the_ends() {
+	let report: SearchReport = search("{}deposit(uint256)", Alphabet::Hex);
+	for res in &report.results[1..] {
+		assert!(res.signature.ends_with("deposit(uint256)"), "{}", res.signature);
+		assert!(!res.signature.starts_with(|c: char| c.is_ascii_digit()), "{}", res.signature);
+	}
+
+	let report: SearchReport = search("deposit{}(uint256)", Alphabet::Lowercase);
+	for res in &report.results[1..] {
+		assert!(res.signature.starts_with("deposit") && res.signature.ends_with("(uint256)"), "{}", res.signature);
+		assert!(!res.signature.contains('_'), "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn invalid_placeholders() {
+	for signature in ["deposit(uint{}256)", "de{}pos{}it(uint256)", "deposit{(uint256)", "{}(uint256)x{}"] {
+		assert!(matches!(Globals::new(signature).err(), Some(ConfigError::InvalidSignature(_))), "{}", signature);
+	}
+
+	// A name starting with a digit, before the placeholder
+	let g: Globals = Globals::new("2{}x(uint256)").unwrap();
+	assert!(matches!(Searcher::new(g).err(), Some(ConfigError::InvalidSignature(_))));
+
+	let mut g: Globals = Globals::new("{}2x(uint256)").unwrap();
+	assert!(Searcher::new(g.clone()).is_ok());
+	g.set_alphabet(Alphabet::custom("0123456789").unwrap());
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::NoLeadingChar("0123456789".to_string())));
+}
+
+
+#[test]
+fn resumed_placeholder() {
+	let mut g: Globals = Globals::new("transfer{}From(address,address,uint256)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 20;
+	g.deterministic = true;
+	assert_eq!(g.template(), "transfer{}From(address,address,uint256)");
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.signature, "transfer{}From(address,address,uint256)");
+
+	g.placeholder = None;
+	assert!(!cp.matches(&g));
+}
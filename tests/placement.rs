@@ -0,0 +1,94 @@
+use select0r::selector::signature_result;
+use select0r::{Alphabet, Checkpoint, ConfigError, Globals, Placement, SearchReport, Searcher, StopReason};
+
+mod common;
+
+
+fn search(placement: Placement, separator: &str, alphabet: Alphabet) -> SearchReport {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = 40;
+	g.deterministic = true;
+	g.placement     = placement;
+	g.separator     = separator.to_string();
+	g.set_alphabet(alphabet);
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn suffix_separators() {
+	for separator in ["_", "", "__", "x"] {
+		let report: SearchReport = search(Placement::Suffix, separator, Alphabet::Base64);
+		for res in &report.results[1..] {
+			let prefix: String = format!("deposit{}", separator);
+			assert!(res.signature.starts_with(&prefix) && res.signature.ends_with("(uint256)"), "{}", res.signature);
+		}
+	}
+}
+
+
+#[test]
+fn prefix_never_starts_with_a_digit() {
+	for (separator, alphabet) in [("_", Alphabet::Base64), ("", Alphabet::Hex), ("$", Alphabet::custom("0123x").unwrap())] {
+		let report: SearchReport = search(Placement::Prefix, separator, alphabet.clone());
+		for res in &report.results[1..] {
+			let suffix: String = format!("{}deposit(uint256)", separator);
+			assert!(res.signature.ends_with(&suffix), "{}", res.signature);
+			assert!(!res.signature.starts_with(|c: char| c.is_ascii_digit()), "{}", res.signature);
+			let generated: &str = res.signature.trim_end_matches(&suffix);
+			assert!(generated.bytes().all(|c| alphabet.chars().contains(&c)), "{}", res.signature);
+		}
+	}
+
+	// The first character among the 6 letters of `hex`, the others among the 16 characters
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.placement = Placement::Prefix;
+	g.set_alphabet(Alphabet::Hex);
+	assert_eq!(g.pass_len(1), 6);
+	assert_eq!(g.pass_len(3), 6 * 16 * 16);
+}
+
+
+#[test]
+fn invalid_names() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.separator = "-".to_string();
+	assert_eq!(Searcher::new(g.clone()).err(), Some(ConfigError::InvalidSeparator("-".to_string())));
+
+	g.separator = "_".to_string();
+	g.placement = Placement::Prefix;
+	g.set_alphabet(Alphabet::custom("0123456789").unwrap());
+	assert_eq!(Searcher::new(g).err(), Some(ConfigError::NoLeadingChar("0123456789".to_string())));
+
+	// A name starting with a digit is only valid behind a prefix
+	let mut g: Globals = Globals::new("2x(uint256)").unwrap();
+	assert!(matches!(Searcher::new(g.clone()).err(), Some(ConfigError::InvalidSignature(_))));
+	g.placement = Placement::Prefix;
+	assert!(Searcher::new(g).is_ok());
+}
+
+
+#[test]
+fn resumed_placement() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 20;
+	g.deterministic = true;
+	g.placement     = Placement::Prefix;
+	g.separator     = String::new();
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.placement, Placement::Prefix);
+	assert_eq!(cp.separator, "");
+
+	g.separator = "_".to_string();
+	assert!(!cp.matches(&g));
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use select0r::planner::{self, Plan};
+use select0r::selector::zeros_probability;
+use select0r::Globals;
+
+
+#[test]
+fn expected_attempts() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 2;
+	g.max_results = 12;
+
+	let plan: Plan = planner::plan(&g, 1_000_000.0);
+	assert_eq!(plan.needed, 11);
+	assert!((plan.attempts - 11.0 / zeros_probability(2)).abs() < 1.0);
+	assert_eq!(plan.duration.map(|d| d.as_secs()), Some((plan.attempts / 1_000_000.0) as u64));
+	assert!(!plan.unlikely());
+
+	// 64^3 suffixes are enough for 11 results with 2 zeros, not 64 + 64^2
+	assert_eq!(plan.goal_pass, Some(3));
+	assert_eq!(plan.passes.len(), g.digit_max as usize);
+	assert_eq!(plan.passes[2].keyspace, g.pass_len(3));
+	assert_eq!(plan.passes[2].attempts, g.pass_start(4) as f64);
+}
+
+
+#[test]
+fn leading_zeros_records() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.leading0    = true;
+	g.max_results = 8;
+
+	// Each result must beat all the previous ones, much more costly than any 7 results
+	let plan: Plan = planner::plan(&g, 0.0);
+	assert!(plan.attempts > 100.0 * 7.0 / zeros_probability(1));
+	assert_eq!(plan.duration, None);
+	assert!(!plan.unlikely());
+	for pair in plan.passes.windows(2) {
+		assert!(pair[0].results <= pair[1].results);
+	}
+
+	// About ln(n) records among n hits, far fewer than 100 within the passes
+	g.max_results = 100;
+	let plan: Plan = planner::plan(&g, 0.0);
+	assert!(plan.unlikely());
+	assert_eq!(plan.goal_pass, None);
+	assert!(plan.reach_probability < 1e-6);
+}
+
+
+#[test]
+fn stop_leading_zeros() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty       = 1;
+	g.min_leading_zero = Some(4);
+	assert_eq!(planner::plan(&g, 0.0).stop_attempts, Some(2f64.powi(32)));
+
+	g.min_leading_zero = None;
+	assert_eq!(planner::plan(&g, 0.0).stop_attempts, None);
+}
+
+
+#[test]
+fn measured_rate() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.nn_threads = 1;
+	assert!(planner::measure(&g, Duration::from_millis(100)).unwrap() > 0.0);
+
+	g.nn_threads = 0;
+	assert!(planner::measure(&g, Duration::from_millis(100)).is_err());
+}
@@ -0,0 +1,270 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use select0r::selector::{signature_result, zeros_probability};
+use select0r::{Checkpoint, ConfigError, Event, Globals, IteratedValue, Output, Progress, SearchReport, Searcher, StopReason};
+
+
+#[test]
+fn ten_digits_max() {
+	let g: Globals = Globals::new("mint(uint256)").unwrap();
+	assert_eq!(g.digit_max, 10);
+}
+
+
+#[test]
+fn long_suffixes() {
+	// Resumed at the end of the 7 digits pass, so the search goes on with 8 digits suffixes
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.nn_threads  = 2;
+	g.max_results = 8;
+	let end: IteratedValue = g.pass_len(7);
+	let checkpoint: Checkpoint = Checkpoint {
+		signature    : g.template(),
+		difficulty   : g.difficulty,
+		leading0     : g.leading0,
+		max_results  : g.max_results,
+		deterministic: g.deterministic,
+		alphabet     : String::from_utf8_lossy(g.alphabet.chars()).into_owned(),
+		pattern      : String::new(),
+		words        : vec![],
+		placement    : g.placement,
+		separator    : g.separator.clone(),
+		cases        : None,
+		permutation  : None,
+		type_suffix  : None,
+		output       : g.output.clone(),
+		nn_threads   : g.nn_threads,
+		digit        : 7,
+		next         : g.pass_start(7) + end - 400,
+		pending      : vec![],
+		optimal      : u32::MAX,
+		hashes       : 0,
+		results      : vec![signature_result(&g.signature)],
+	};
+
+	let report: SearchReport = Searcher::resume(g, checkpoint).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	assert_eq!(report.results.len(), 8);
+
+	let suffixes: Vec<&str> = report.results[1..].iter()
+		.map(|res| res.signature.trim_start_matches("mint_").trim_end_matches("(uint256)"))
+		.collect();
+	assert!(suffixes.iter().any(|suffix| suffix.len() == 7), "{:?}", suffixes);
+	assert!(suffixes.iter().any(|suffix| suffix.len() == 8), "{:?}", suffixes);
+	for res in &report.results[1..] {
+		let expected = signature_result(&res.signature);
+		assert_eq!(res.selector, expected.selector, "{}", res.signature);
+		assert!(res.nbr_of_zero >= 1, "{}", res.signature);
+	}
+}
+
+
+/// Runs a search until it spends `max_hashes` more hashes, from `checkpoint` if any.
+fn run_until(threads: usize, max_hashes: u64, checkpoint: Option<Checkpoint>) -> (SearchReport, Option<Checkpoint>) {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.nn_threads  = threads;
+	g.max_results = 1_000_000;
+	g.max_hashes  = Some(max_hashes);
+
+	let searcher: Searcher = match checkpoint {
+		Some(cp) => Searcher::resume(g, cp).unwrap(),
+		None     => Searcher::new(g).unwrap(),
+	};
+	let last: Mutex<Option<Checkpoint>> = Mutex::new(None);
+	let report: SearchReport = searcher.run_with(|event| {
+		if let Event::Checkpoint(cp) = event {
+			*last.lock().unwrap() = Some(cp.clone());
+		}
+	});
+	(report, last.into_inner().unwrap())
+}
+
+
+fn signatures(report: &SearchReport) -> Vec<String> {
+	let mut signatures: Vec<String> = report.results.iter().map(|res| res.signature.clone()).collect();
+	signatures.sort();
+	signatures
+}
+
+
+#[test]
+fn resumed_same_as_uninterrupted() {
+	let (whole, _) = run_until(1, 100_000, None);
+	assert_eq!(whole.stop, StopReason::HashBudget);
+
+	// Stopped and resumed twice, by several threads, each run with its own budget
+	let (first, cp)  = run_until(3, 25_000, None);
+	let (second, cp) = run_until(2, 35_000, cp);
+	let (last, _)    = run_until(1, 100_000 - second.hashes, cp);
+	assert_eq!(first.stop, StopReason::HashBudget);
+	assert_eq!(second.stop, StopReason::HashBudget);
+	assert!(second.hashes - first.hashes <= 35_000);
+	assert_eq!(last.hashes, 100_000);
+
+	// The last run, with a single thread, fills the gaps left by the others before going on
+	assert_eq!(signatures(&last), signatures(&whole));
+	assert!(whole.results.len() > 1);
+}
+
+
+#[test]
+fn leading_zeros_always_better() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.nn_threads  = 4;
+	g.leading0    = true;
+	g.max_results = 6;
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	assert_eq!(report.results.len(), 6);
+	for pair in report.results.windows(2) {
+		assert!(pair[1].selector < pair[0].selector, "{:08x} after {:08x}", pair[1].selector, pair[0].selector);
+	}
+}
+
+
+#[test]
+fn max_results_exactly() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.nn_threads  = 4;
+	g.max_results = 20;
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	assert_eq!(report.results.len(), 20);
+}
+
+
+#[test]
+fn probability_of_zeros() {
+	assert_eq!(zeros_probability(0), 1.0);
+	assert!((zeros_probability(1) - 0.015_5).abs() < 1e-4);
+	assert!((zeros_probability(2) * 65_536.0 - 6.0).abs() < 0.1);
+	assert_eq!(zeros_probability(4), 1.0 / 256f64.powi(4));
+}
+
+
+#[test]
+fn progress_every() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty     = 3;
+	g.nn_threads     = 2;
+	g.max_results    = 1_000_000;
+	g.progress_every = Some(Duration::from_millis(50));
+	g.deadline       = Some(Duration::from_millis(400));
+
+	let last: Mutex<Vec<Progress>> = Mutex::new(vec![]);
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|event| {
+		if let Event::Progress(p) = event {
+			last.lock().unwrap().push(p.clone());
+		}
+	});
+	assert_eq!(report.stop, StopReason::Deadline);
+
+	let progress: Vec<Progress> = last.into_inner().unwrap();
+	assert!(progress.len() >= 2, "{}", progress.len());
+	for pair in progress.windows(2) {
+		assert!(pair[0].hashes <= pair[1].hashes);
+		assert!(pair[0].elapsed < pair[1].elapsed);
+	}
+	let p: &Progress = progress.last().unwrap();
+	assert!(p.hashes_per_sec > 0.0);
+	assert!((0.0..=1.0).contains(&p.pass_done));
+	assert!(p.next_hit.is_some());
+}
+
+
+/// Runs a deterministic search with `threads` threads, from `checkpoint` if any.
+fn run_ordered(leading0: bool, threads: usize, max_hashes: Option<u64>, checkpoint: Option<Checkpoint>) -> (SearchReport, Option<Checkpoint>) {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = threads;
+	g.leading0      = leading0;
+	g.max_results   = if leading0 { 6 } else { 20 };
+	g.deterministic = true;
+	g.max_hashes    = max_hashes;
+
+	let searcher: Searcher = match checkpoint {
+		Some(cp) => Searcher::resume(g, cp).unwrap(),
+		None     => Searcher::new(g).unwrap(),
+	};
+	let last: Mutex<Option<Checkpoint>> = Mutex::new(None);
+	let report: SearchReport = searcher.run_with(|event| {
+		if let Event::Checkpoint(cp) = event {
+			*last.lock().unwrap() = Some(cp.clone());
+		}
+	});
+	(report, last.into_inner().unwrap())
+}
+
+
+fn ordered(report: &SearchReport) -> Vec<(Option<IteratedValue>, String)> {
+	report.results.iter().map(|res| (res.index, res.signature.clone())).collect()
+}
+
+
+#[test]
+fn deterministic_whatever_the_threads() {
+	for leading0 in [false, true] {
+		let (single, _) = run_ordered(leading0, 1, None, None);
+		assert_eq!(single.stop, StopReason::Goal);
+		assert_eq!(single.results.len(), if leading0 { 6 } else { 20 });
+		for pair in single.results[1..].windows(2) {
+			assert!(pair[0].index < pair[1].index);
+			assert!(!leading0 || pair[1].selector < pair[0].selector);
+		}
+
+		for threads in [2, 5, 8] {
+			let (many, _) = run_ordered(leading0, threads, None, None);
+			assert_eq!(many.stop, StopReason::Goal);
+			assert_eq!(ordered(&many), ordered(&single), "{} threads, leading0 {}", threads, leading0);
+		}
+	}
+}
+
+
+#[test]
+fn deterministic_resumed() {
+	let (whole, _) = run_ordered(true, 1, None, None);
+
+	// Stopped half way, with the later chunks of some threads searched before the earlier ones of others
+	let (first, cp) = run_ordered(true, 4, Some(whole.hashes / 2), None);
+	assert_eq!(first.stop, StopReason::HashBudget);
+	let cp: Checkpoint = cp.unwrap();
+	assert!(cp.deterministic);
+	let cp: Checkpoint = cp.to_string().parse().unwrap();
+
+	let (last, _) = run_ordered(true, 3, None, Some(cp));
+	assert_eq!(last.stop, StopReason::Goal);
+	assert_eq!(ordered(&last), ordered(&whole));
+}
+
+
+#[test]
+fn resumed_output() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty  = 1;
+	g.max_results = 1_000_000;
+	g.output      = Output::JSON;
+	g.max_hashes  = Some(5_000);
+
+	let last: Mutex<Option<Checkpoint>> = Mutex::new(None);
+	Searcher::new(g.clone()).unwrap().run_with(|event| {
+		if let Event::Checkpoint(cp) = event {
+			*last.lock().unwrap() = Some(cp.clone());
+		}
+	});
+	let cp: Checkpoint = last.into_inner().unwrap().unwrap().to_string().parse().unwrap();
+	assert_eq!(cp.output, Output::JSON);
+	assert!(cp.matches(&g));
+
+	// The results go on in the same file
+	g.output = Output::TSV;
+	assert!(!cp.matches(&g));
+	assert_eq!(Searcher::resume(g, cp).err(), Some(ConfigError::CheckpointMismatch));
+}
@@ -0,0 +1,76 @@
+use select0r::selector::signature_result;
+use select0r::syllable::{self, CONSONANTS, VOWELS};
+use select0r::{Globals, Pattern, SearchReport, Searcher, StopReason};
+
+
+fn search(pattern: Pattern) -> SearchReport {
+	let mut g: Globals = Globals::new("mint(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = 20;
+	g.deterministic = true;
+	g.set_pattern(pattern);
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+/// The generated characters, consonants and vowels in turn.
+fn alternate(signature: &str, consonants: &[&str], vowels: &[&str]) -> bool {
+	let name: &str = signature.strip_prefix("mint_").unwrap().strip_suffix("(uint256)").unwrap();
+	name.to_lowercase().chars().enumerate().all(|(i, c)| {
+		let table: &[&str] = if i % 2 == 0 { consonants } else { vowels };
+		table.contains(&c.to_string().as_str())
+	})
+}
+
+
+#[test]
+fn default_tables() {
+	let tables: Vec<(String, Vec<String>)> = syllable::with_tables(&[]);
+	let syllables: &Vec<String>            = &tables.iter().find(|(name, _)| name == "syllable").unwrap().1;
+	assert_eq!(syllables.len(), CONSONANTS.len() * VOWELS.len());
+	assert!(syllables.contains(&"ka".to_string()) && syllables.contains(&"zu".to_string()));
+
+	let report: SearchReport = search(Pattern::new("{syllable}{2}").unwrap());
+	for res in &report.results[1..] {
+		assert_eq!(res.signature.len(), "mint_kabo(uint256)".len(), "{}", res.signature);
+		assert!(alternate(&res.signature, &CONSONANTS, &VOWELS), "{}", res.signature);
+	}
+
+	let report: SearchReport = search(Pattern::new("{Syllable}{2}{consonant}").unwrap());
+	for res in &report.results[1..] {
+		assert_eq!(res.signature.len(), "mint_Zulef(uint256)".len(), "{}", res.signature);
+		assert!(alternate(&res.signature, &CONSONANTS, &VOWELS), "{}", res.signature);
+		assert!(res.signature[5..].starts_with(|c: char| c.is_ascii_uppercase()), "{}", res.signature);
+	}
+}
+
+
+#[test]
+fn replaced_tables() {
+	// Other vowels, the syllables follow
+	let vowels: Vec<(String, Vec<String>)> = vec![("vowel".to_string(), vec!["a".to_string(), "o".to_string()])];
+	let mut g: Globals                     = Globals::new("mint(uint256)").unwrap();
+	g.set_pattern(Pattern::with_words("{syllable}{2}", vowels.clone()).unwrap());
+	assert_eq!(g.keyspace(), (CONSONANTS.len() as u64 * 2).pow(2));
+	for res in &search(Pattern::with_words("{syllable}{2,3}", vowels).unwrap()).results[1..] {
+		assert!(alternate(&res.signature, &CONSONANTS, &["a", "o"]), "{}", res.signature);
+	}
+
+	// Other syllables, of any length
+	let syllables: Vec<String> = ["zu", "le", "fa", "dor", "kin"].iter().map(|s| s.to_string()).collect();
+	let pattern: Pattern       = Pattern::with_words("{syllable}{1,2}", vec![("syllable".to_string(), syllables)]).unwrap();
+	let mut g: Globals         = Globals::new("mint(uint256)").unwrap();
+	g.set_pattern(pattern);
+	assert_eq!(g.keyspace(), 5 + 5 * 5);
+	assert_eq!(g.pass_len(1), 3);
+
+	// Only the lists given are kept, the default tables are always there
+	assert!(Pattern::new("{syllable}").unwrap().words().is_empty());
+}
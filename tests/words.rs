@@ -0,0 +1,133 @@
+use select0r::selector::signature_result;
+use select0r::{Checkpoint, ConfigError, Globals, Pattern, SearchReport, Searcher, StopReason};
+
+mod common;
+
+
+const WORDS: [&str; 40] = [
+	"add", "all", "amount", "approve", "balance", "batch", "burn", "call", "claim", "close",
+	"deposit", "escrow", "exit", "fee", "fund", "grant", "hold", "join", "lock", "mint",
+	"move", "open", "order", "owner", "pay", "pool", "price", "quote", "rate", "redeem",
+	"send", "settle", "stake", "swap", "take", "token", "trade", "unlock", "vote", "withdraw",
+];
+
+
+fn words() -> Vec<(String, Vec<String>)> {
+	vec![("word".to_string(), WORDS.iter().map(|word| word.to_string()).collect())]
+}
+
+
+fn search(pattern: &str, separator: &str, max_results: usize) -> SearchReport {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.nn_threads    = 2;
+	g.max_results   = max_results;
+	g.deterministic = true;
+	g.separator     = separator.to_string();
+	g.set_pattern(Pattern::with_words(pattern, words()).unwrap());
+
+	let report: SearchReport = Searcher::new(g).unwrap().run_with(|_| {});
+	assert_eq!(report.results[0].signature, "deposit(uint256)");
+	for res in &report.results {
+		assert_eq!(res.selector, signature_result(&res.signature).selector, "{}", res.signature);
+	}
+	report
+}
+
+
+#[test]
+fn cased_words() {
+	let report: SearchReport = search("{Word}[0-9]{0,2}", "", 10);
+	assert_eq!(report.stop, StopReason::Goal);
+	for res in &report.results[1..] {
+		let name: &str = res.signature.strip_prefix("deposit").unwrap().strip_suffix("(uint256)").unwrap();
+		let word: &str = name.trim_end_matches(|c: char| c.is_ascii_digit());
+		assert!(WORDS.iter().any(|w| w[..1].to_uppercase() + &w[1..] == word), "{}", res.signature);
+	}
+
+	let report: SearchReport = search("{word}_{WORD}", "_", 10);
+	for res in &report.results[1..] {
+		let name: &str                   = res.signature.strip_prefix("deposit_").unwrap().strip_suffix("(uint256)").unwrap();
+		let (lower, upper): (&str, &str) = name.split_once('_').unwrap();
+		assert!(WORDS.contains(&lower) && WORDS.contains(&upper.to_lowercase().as_str()), "{}", res.signature);
+		assert_eq!(upper, upper.to_uppercase());
+	}
+}
+
+
+#[test]
+fn passes_by_word_lengths() {
+	let pattern: Pattern = Pattern::with_words("{word}_{word}", words()).unwrap();
+	let lengths: Vec<usize> = {
+		let mut lengths: Vec<usize> = WORDS.iter().map(|word| word.len()).collect();
+		lengths.sort_unstable();
+		lengths.dedup();
+		lengths
+	};
+	assert_eq!(pattern.passes() as usize, lengths.len() * lengths.len());
+
+	// The shortest names first, every pair of words once
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.set_pattern(pattern);
+	let widths: Vec<usize> = (1..=g.digit_max).map(|pass| g.classes(pass).iter().map(|class| class.width()).sum()).collect();
+	assert!(widths.windows(2).all(|w| w[0] <= w[1]));
+	assert_eq!(g.keyspace(), 40 * 40);
+}
+
+
+#[test]
+fn readable_first() {
+	let report: SearchReport = search("{word}[0-9]{0,2}", "_", 30);
+	let noise = |signature: &str| signature.trim_end_matches("(uint256)").chars().rev().take_while(char::is_ascii_digit).count();
+	let ranks: Vec<(std::cmp::Reverse<u32>, usize, usize)> = report.results[1..].iter()
+		.map(|res| (std::cmp::Reverse(res.nbr_of_zero), noise(&res.signature), res.signature.len()))
+		.collect();
+	assert!(ranks.windows(2).all(|w| w[0] <= w[1]), "{:?}", ranks);
+}
+
+
+#[test]
+fn ties_by_noise_then_length() {
+	// Among the results with 1 zero byte, the fewest digits first, then the shortest names
+	let report: SearchReport = search("{word}[0-9]{0,2}", "_", 30);
+	let noise = |signature: &str| signature.trim_end_matches("(uint256)").chars().rev().take_while(char::is_ascii_digit).count();
+	let ties: Vec<(usize, usize)> = report.results[1..].iter()
+		.filter(|res| res.nbr_of_zero == 1)
+		.map(|res| (noise(&res.signature), res.signature.len()))
+		.collect();
+	assert!(ties.windows(2).all(|w| w[0] <= w[1]), "{:?}", ties);
+
+	// Both keys settle some ties
+	assert!(ties.windows(2).any(|w| w[0].0 < w[1].0), "{:?}", ties);
+	assert!(ties.windows(2).any(|w| w[0].0 == w[1].0 && w[0].1 < w[1].1), "{:?}", ties);
+}
+
+
+#[test]
+fn invalid_words() {
+	assert_eq!(Pattern::with_words("{noun}", words()).err(), Some(ConfigError::InvalidPattern("{noun}".to_string())));
+	assert_eq!(Pattern::with_words("{word}", vec![("word".to_string(), vec![])]).err(), Some(ConfigError::InvalidWord("word".to_string())));
+	assert_eq!(
+		Pattern::with_words("{word}", vec![("word".to_string(), vec!["set".to_string(), "get-all".to_string()])]).err(),
+		Some(ConfigError::InvalidWord("get-all".to_string())),
+	);
+}
+
+
+#[test]
+fn resumed_words() {
+	let mut g: Globals = Globals::new("deposit(uint256)").unwrap();
+	g.difficulty    = 1;
+	g.max_results   = 10;
+	g.deterministic = true;
+	g.set_pattern(Pattern::with_words("{word}{Word}", words()).unwrap());
+
+	let cp: Checkpoint = common::resumed_same_as_uninterrupted(&g);
+	assert_eq!(cp.pattern, "{word}{Word}");
+	assert_eq!(cp.words, words());
+
+	let mut other: Vec<(String, Vec<String>)> = words();
+	other[0].1.pop();
+	g.set_pattern(Pattern::with_words("{word}{Word}", other).unwrap());
+	assert!(!cp.matches(&g));
+}